@@ -2,7 +2,7 @@ use std::{
     borrow::Cow,
     fmt::Display,
     fs::File,
-    io::{stdin, Read},
+    io::{self, stdin, Read},
     iter,
     path::PathBuf,
     process::exit,
@@ -11,14 +11,19 @@ use std::{
 use chrono_tz::Tz;
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use console::style;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 
 use ocpi_tariffs::{
     ocpi::{cdr::Cdr, tariff::OcpiTariff, v211},
     pricer::{Pricer, Report},
+    types::money::{Money, Price},
 };
 
-use crate::{error::Error, Result};
+use crate::{
+    client::{HttpOcpiClient, OcpiClient},
+    error::Error,
+    Result,
+};
 
 #[derive(Parser)]
 pub struct Cli {
@@ -48,6 +53,12 @@ pub enum Command {
     ///
     /// This command will show you a breakdown of all the calculated costs.
     Analyze(Analyze),
+    /// Fetch CDRs and tariffs directly from an OCPI platform and reconcile their costs.
+    ///
+    /// This pulls every CDR and tariff from the platform's `cdrs` and `tariffs` module
+    /// endpoints, following pagination, and validates each CDR's cost against the calculated
+    /// cost, just like `validate` does for a single file.
+    Fetch(Fetch),
 }
 
 impl Command {
@@ -55,6 +66,7 @@ impl Command {
         match self {
             Self::Validate(args) => args.run(),
             Self::Analyze(args) => args.run(),
+            Self::Fetch(args) => args.run(),
         }
     }
 }
@@ -84,6 +96,27 @@ pub struct TariffArgs {
     /// use `detect` to let to tool try to find the matching version.
     #[arg(short = 'o', long, value_enum, default_value_t = OcpiVersion::default())]
     ocpi_version: OcpiVersion,
+    /// Reject malformed or ambiguous input instead of silently tolerating it.
+    ///
+    /// In strict mode, unknown fields on the CDR/tariff structures are rejected, and when
+    /// `--ocpi-version detect` is used, input that parses successfully as both 2.1.1 and 2.2.1
+    /// but yields different structures is reported as an ambiguity instead of silently picking
+    /// one.
+    #[arg(long)]
+    strict: bool,
+    /// Override the rounding scale (number of decimals) used for calculated costs.
+    ///
+    /// By default the scale is derived from the CDR's currency (e.g. 2 for EUR, 0 for JPY).
+    #[arg(long)]
+    scale: Option<u32>,
+    /// Treat a calculated total as matching the CDR's total when they differ by no more than
+    /// this amount, instead of requiring exact equality.
+    ///
+    /// This only applies to monetary totals; CPOs often round costs to a different granularity
+    /// than this tool, and a tight tolerance lets `validate` reconcile those without masking a
+    /// genuine pricing error.
+    #[arg(long, default_value_t = rust_decimal::Decimal::ZERO)]
+    tolerance: rust_decimal::Decimal,
 }
 
 impl TariffArgs {
@@ -105,20 +138,28 @@ impl TariffArgs {
         let cdr: Cdr = if let Some(cdr_path) = &self.cdr {
             let file = File::open(cdr_path).map_err(|e| Error::file(cdr_path.clone(), e))?;
 
-            from_reader_with_version::<_, _, v211::cdr::Cdr>(file, self.ocpi_version)
+            from_reader_with_version::<_, _, v211::cdr::Cdr>(file, self.ocpi_version, self.strict)
                 .map_err(|e| Error::deserialize(cdr_path.display(), "CDR", e))?
         } else {
             let mut stdin = stdin().lock();
-            from_reader_with_version::<_, _, v211::cdr::Cdr>(&mut stdin, self.ocpi_version)
-                .map_err(|e| Error::deserialize("<stdin>", "CDR", e))?
+            from_reader_with_version::<_, _, v211::cdr::Cdr>(
+                &mut stdin,
+                self.ocpi_version,
+                self.strict,
+            )
+            .map_err(|e| Error::deserialize("<stdin>", "CDR", e))?
         };
 
         let tariff: Option<OcpiTariff> = if let Some(path) = &self.tariff {
             let file = File::open(path).map_err(|e| Error::file(path.clone(), e))?;
 
             Some(
-                from_reader_with_version::<_, _, v211::tariff::OcpiTariff>(file, self.ocpi_version)
-                    .map_err(|e| Error::deserialize(path.display(), "tariff", e))?,
+                from_reader_with_version::<_, _, v211::tariff::OcpiTariff>(
+                    file,
+                    self.ocpi_version,
+                    self.strict,
+                )
+                .map_err(|e| Error::deserialize(path.display(), "tariff", e))?,
             )
         } else {
             None
@@ -134,6 +175,10 @@ impl TariffArgs {
             pricer = pricer.with_time_zone(time_zone);
         }
 
+        if let Some(scale) = self.scale {
+            pricer = pricer.with_scale(scale);
+        }
+
         let report = pricer.build_report().map_err(Error::Internal)?;
 
         Ok((report, cdr, tariff))
@@ -143,28 +188,93 @@ impl TariffArgs {
 pub fn from_reader_with_version<R, T0, T1>(
     mut reader: R,
     version: OcpiVersion,
+    strict: bool,
 ) -> std::io::Result<T0>
 where
     R: Read,
-    T0: DeserializeOwned + From<T1>,
+    T0: DeserializeOwned + From<T1> + PartialEq,
     T1: DeserializeOwned,
 {
+    let mut content = Vec::new();
+    reader.read_to_end(&mut content)?;
+
     match version {
-        OcpiVersion::V221 => Ok(serde_json::from_reader::<R, T0>(reader)?),
-        OcpiVersion::V211 => Ok(serde_json::from_reader::<R, T1>(reader)?.into()),
+        OcpiVersion::V221 => deserialize_one::<T0>(&content, strict),
+        OcpiVersion::V211 => Ok(deserialize_one::<T1>(&content, strict)?.into()),
+        OcpiVersion::Detect if !strict => match deserialize_one::<T0>(&content, strict) {
+            Ok(v221) => Ok(v221),
+            Err(_) => Ok(deserialize_one::<T1>(&content, strict)?.into()),
+        },
         OcpiVersion::Detect => {
-            let mut content = Vec::new();
-            reader.read_to_end(&mut content)?;
-
-            serde_json::from_slice::<T0>(&content).or_else(|err| {
-                Ok(serde_json::from_slice::<T1>(&content)
-                    .map_err(|_old_err| err)?
-                    .into())
-            })
+            let as_v221 = deserialize_one::<T0>(&content, strict);
+            let as_v211 = deserialize_one::<T1>(&content, strict);
+
+            match (as_v221, as_v211) {
+                (Ok(v221), Ok(v211)) => {
+                    let v211_as_v221: T0 = v211.into();
+
+                    if v221 != v211_as_v221 {
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "input parses as both OCPI 2.1.1 and 2.2.1, but yields different \
+                             structures; specify --ocpi-version explicitly",
+                        ))
+                    } else {
+                        Ok(v221)
+                    }
+                }
+                (Ok(v221), Err(_)) => Ok(v221),
+                (Err(_), Ok(v211)) => Ok(v211.into()),
+                (Err(err), Err(_)) => Err(err),
+            }
         }
     }
 }
 
+/// Deserialize a single JSON value from `content`, rejecting any trailing non-whitespace bytes
+/// that follow it.
+///
+/// In `strict` mode, also rejects fields present in `content` that `T` doesn't recognize, rather
+/// than silently ignoring them.
+fn deserialize_one<T: DeserializeOwned>(content: &[u8], strict: bool) -> std::io::Result<T> {
+    if strict {
+        let mut de = serde_json::Deserializer::from_slice(content);
+        let mut unknown_field = None;
+
+        let value: T = serde_ignored::deserialize(&mut de, |path| {
+            if unknown_field.is_none() {
+                unknown_field = Some(path.to_string());
+            }
+        })?;
+
+        de.end()?;
+
+        if let Some(path) = unknown_field {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unknown field `{path}`"),
+            ));
+        }
+
+        Ok(value)
+    } else {
+        let mut values = serde_json::Deserializer::from_slice(content).into_iter::<T>();
+
+        let value = values
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "empty input"))??;
+
+        if values.next().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "trailing data after the JSON value",
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
 #[derive(Clone, Copy, Default, ValueEnum)]
 pub enum OcpiVersion {
     V221,
@@ -173,161 +283,335 @@ pub enum OcpiVersion {
     Detect,
 }
 
+/// The format in which a command writes its output.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// A human readable ASCII table, printed alongside some additional explanatory text.
+    #[default]
+    Table,
+    /// A single JSON object, suitable for piping into another tool.
+    Json,
+    /// Comma separated values, with a header row.
+    Csv,
+}
+
+/// Quote a CSV field if it contains a comma, a quote or a newline, escaping any quotes within it.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[derive(Parser)]
 pub struct Validate {
     #[command(flatten)]
     args: TariffArgs,
+    /// The format in which to write the validation result.
+    #[arg(short = 'f', long, value_enum, default_value_t = OutputFormat::default())]
+    format: OutputFormat,
 }
 
-impl Validate {
-    fn run(self) -> Result<()> {
-        let (report, cdr, _) = self.args.load_all()?;
+/// Whether a calculated value and the corresponding CDR value agree, per the configured
+/// `--tolerance`.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum MatchState {
+    /// The values are bit-for-bit equal.
+    Exact,
+    /// The values differ, but by no more than `--tolerance`.
+    WithinTolerance,
+    /// The values differ by more than `--tolerance`, or one side is missing.
+    Mismatch,
+}
 
-        println!(
-            "\n{} `{}` with tariff `{}`, using timezone `{}`:",
-            style("Validating").green().bold(),
-            style(self.args.cdr_name()).blue(),
-            style(self.args.tariff_name()).blue(),
-            style(&report.time_zone).blue(),
-        );
+impl MatchState {
+    fn is_valid(self) -> bool {
+        self != Self::Mismatch
+    }
 
-        let mut table = Table::new();
-        let mut is_valid = false;
+    /// Combine two independently compared components of the same property (e.g. a price's
+    /// `excl_vat` and `incl_vat`) into a single state, keeping the worse of the two.
+    fn combine(self, other: Self) -> Self {
+        self.max(other)
+    }
+}
 
-        table.header(&["Property", "Report", "Cdr"]);
+impl Ord for MatchState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        fn rank(state: MatchState) -> u8 {
+            match state {
+                MatchState::Exact => 0,
+                MatchState::WithinTolerance => 1,
+                MatchState::Mismatch => 2,
+            }
+        }
 
-        table.row(&[
-            "Total Time".into(),
-            report.total_time.to_string(),
-            cdr.total_time.to_string(),
-        ]);
+        rank(*self).cmp(&rank(*other))
+    }
+}
 
-        is_valid &= report.total_time == cdr.total_time;
+impl PartialOrd for MatchState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        table.row(&[
-            "Total Parking Time".into(),
-            report.total_parking_time.to_string(),
-            to_string_or_default(cdr.total_parking_time),
-        ]);
+impl Display for MatchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = match self {
+            Self::Exact => "exact",
+            Self::WithinTolerance => "within tolerance",
+            Self::Mismatch => "mismatch",
+        };
 
-        is_valid &= cdr
-            .total_parking_time
-            .map(|c| c == report.total_parking_time)
-            .unwrap_or(true);
+        f.write_str(display)
+    }
+}
 
-        table.row(&[
-            "Total Energy".into(),
-            report.total_energy.with_scale().to_string(),
-            cdr.total_energy.to_string(),
-        ]);
+/// Compare an exact (non-monetary) property, which always requires bit-for-bit equality.
+fn compare_exact(matches: bool) -> MatchState {
+    if matches {
+        MatchState::Exact
+    } else {
+        MatchState::Mismatch
+    }
+}
 
-        is_valid &= report.total_energy == cdr.total_energy;
+/// Compare two monetary amounts, allowing them to differ by up to `tolerance`.
+fn compare_money(report: Money, cdr: Money, tolerance: rust_decimal::Decimal) -> MatchState {
+    if report == cdr {
+        MatchState::Exact
+    } else if (rust_decimal::Decimal::from(report) - rust_decimal::Decimal::from(cdr)).abs()
+        <= tolerance
+    {
+        MatchState::WithinTolerance
+    } else {
+        MatchState::Mismatch
+    }
+}
 
-        table.row(&[
-            "Total Cost (Excl.)".into(),
-            to_string_or_default(report.total_cost.map(|p| p.excl_vat)),
-            cdr.total_cost.with_scale().excl_vat.to_string(),
-        ]);
+/// Compare two [`Price`]s (an `excl_vat`/`incl_vat` pair), allowing either component to differ
+/// by up to `tolerance`.
+fn compare_price(report: Price, cdr: Price, tolerance: rust_decimal::Decimal) -> MatchState {
+    let excl_vat = compare_money(report.excl_vat, cdr.excl_vat, tolerance);
 
-        table.row(&[
-            "Total Cost (Incl.)".into(),
-            to_string_or_default(report.total_cost.and_then(|p| p.incl_vat)),
-            to_string_or_default(cdr.total_cost.incl_vat),
-        ]);
+    let incl_vat = match (report.incl_vat, cdr.incl_vat) {
+        (Some(report), Some(cdr)) => compare_money(report, cdr, tolerance),
+        _ => MatchState::Exact,
+    };
 
-        is_valid &= report
-            .total_cost
-            .map(|p| p == cdr.total_cost)
-            .unwrap_or(true);
+    excl_vat.combine(incl_vat)
+}
 
-        table.row(&[
-            "Total Time Cost (Excl.)".into(),
-            to_string_or_default(report.total_time_cost.map(|p| p.with_scale().excl_vat)),
-            to_string_or_default(cdr.total_time_cost.map(|p| p.excl_vat)),
-        ]);
+/// A single compared property, as shown in a row of the `Validate` report.
+#[derive(Serialize)]
+struct ValidationRow {
+    property: &'static str,
+    report: String,
+    cdr: String,
+    matches: MatchState,
+}
 
-        table.row(&[
-            "Total Time Cost (Incl.)".into(),
-            to_string_or_default(report.total_time_cost.and_then(|p| p.with_scale().incl_vat)),
-            to_string_or_default(cdr.total_time_cost.and_then(|p| p.incl_vat)),
-        ]);
+impl Validate {
+    fn run(self) -> Result<()> {
+        let (report, cdr, _) = self.args.load_all()?;
+        let tolerance = self.args.tolerance;
+
+        let mut rows = Vec::new();
+
+        rows.push(ValidationRow {
+            property: "Total Time",
+            report: report.total_time.to_string(),
+            cdr: cdr.total_time.to_string(),
+            matches: compare_exact(report.total_time == cdr.total_time),
+        });
 
-        is_valid &= report
+        rows.push(ValidationRow {
+            property: "Total Parking Time",
+            report: report.total_parking_time.to_string(),
+            cdr: to_string_or_default(cdr.total_parking_time),
+            matches: compare_exact(
+                cdr.total_parking_time
+                    .map(|c| c == report.total_parking_time)
+                    .unwrap_or(true),
+            ),
+        });
+
+        rows.push(ValidationRow {
+            property: "Total Energy",
+            report: report.total_energy.with_scale().to_string(),
+            cdr: cdr.total_energy.to_string(),
+            matches: compare_exact(report.total_energy == cdr.total_energy),
+        });
+
+        let cost_matches = report
+            .total_cost
+            .map(|p| compare_price(p, cdr.total_cost, tolerance))
+            .unwrap_or(MatchState::Exact);
+
+        rows.push(ValidationRow {
+            property: "Total Cost (Excl.)",
+            report: to_string_or_default(report.total_cost.map(|p| p.excl_vat)),
+            cdr: cdr.total_cost.with_scale().excl_vat.to_string(),
+            matches: cost_matches,
+        });
+
+        rows.push(ValidationRow {
+            property: "Total Cost (Incl.)",
+            report: to_string_or_default(report.total_cost.and_then(|p| p.incl_vat)),
+            cdr: to_string_or_default(cdr.total_cost.incl_vat),
+            matches: cost_matches,
+        });
+
+        let time_cost_matches = report
             .total_time_cost
             .zip(cdr.total_time_cost)
-            .map(|(l, r)| l == r)
-            .unwrap_or(true);
-
-        table.row(&[
-            "Total Fixed Cost (Excl.)".into(),
-            to_string_or_default(report.total_fixed_cost.map(|p| p.excl_vat)),
-            to_string_or_default(cdr.total_fixed_cost.map(|p| p.excl_vat)),
-        ]);
+            .map(|(l, r)| compare_price(l, r, tolerance))
+            .unwrap_or(MatchState::Exact);
+
+        rows.push(ValidationRow {
+            property: "Total Time Cost (Excl.)",
+            report: to_string_or_default(report.total_time_cost.map(|p| p.with_scale().excl_vat)),
+            cdr: to_string_or_default(cdr.total_time_cost.map(|p| p.excl_vat)),
+            matches: time_cost_matches,
+        });
 
-        table.row(&[
-            "Total Fixed Cost (Incl.)".into(),
-            to_string_or_default(report.total_fixed_cost.and_then(|p| p.incl_vat)),
-            to_string_or_default(cdr.total_fixed_cost.and_then(|p| p.incl_vat)),
-        ]);
+        rows.push(ValidationRow {
+            property: "Total Time Cost (Incl.)",
+            report: to_string_or_default(
+                report.total_time_cost.and_then(|p| p.with_scale().incl_vat),
+            ),
+            cdr: to_string_or_default(cdr.total_time_cost.and_then(|p| p.incl_vat)),
+            matches: time_cost_matches,
+        });
 
-        is_valid &= report
+        let fixed_cost_matches = report
             .total_fixed_cost
             .zip(cdr.total_fixed_cost)
-            .map(|(l, r)| l == r)
-            .unwrap_or(true);
-
-        table.row(&[
-            "Total Energy Cost (Excl.)".into(),
-            to_string_or_default(report.total_energy_cost.map(|p| p.excl_vat)),
-            to_string_or_default(cdr.total_energy_cost.map(|p| p.excl_vat)),
-        ]);
+            .map(|(l, r)| compare_price(l, r, tolerance))
+            .unwrap_or(MatchState::Exact);
+
+        rows.push(ValidationRow {
+            property: "Total Fixed Cost (Excl.)",
+            report: to_string_or_default(report.total_fixed_cost.map(|p| p.excl_vat)),
+            cdr: to_string_or_default(cdr.total_fixed_cost.map(|p| p.excl_vat)),
+            matches: fixed_cost_matches,
+        });
 
-        table.row(&[
-            "Total Energy Cost (Incl.)".into(),
-            to_string_or_default(report.total_energy_cost.and_then(|p| p.incl_vat)),
-            to_string_or_default(cdr.total_energy_cost.and_then(|p| p.incl_vat)),
-        ]);
+        rows.push(ValidationRow {
+            property: "Total Fixed Cost (Incl.)",
+            report: to_string_or_default(report.total_fixed_cost.and_then(|p| p.incl_vat)),
+            cdr: to_string_or_default(cdr.total_fixed_cost.and_then(|p| p.incl_vat)),
+            matches: fixed_cost_matches,
+        });
 
-        is_valid &= report
+        let energy_cost_matches = report
             .total_energy_cost
             .zip(cdr.total_energy_cost)
-            .map(|(l, r)| l == r)
-            .unwrap_or(true);
-
-        table.row(&[
-            "Total Parking Cost (Excl.)".into(),
-            to_string_or_default(report.total_parking_cost.map(|p| p.excl_vat)),
-            to_string_or_default(cdr.total_parking_cost.map(|p| p.excl_vat)),
-        ]);
+            .map(|(l, r)| compare_price(l, r, tolerance))
+            .unwrap_or(MatchState::Exact);
+
+        rows.push(ValidationRow {
+            property: "Total Energy Cost (Excl.)",
+            report: to_string_or_default(report.total_energy_cost.map(|p| p.excl_vat)),
+            cdr: to_string_or_default(cdr.total_energy_cost.map(|p| p.excl_vat)),
+            matches: energy_cost_matches,
+        });
 
-        table.row(&[
-            "Total Parking Cost (Incl.)".into(),
-            to_string_or_default(report.total_parking_cost.and_then(|p| p.incl_vat)),
-            to_string_or_default(cdr.total_parking_cost.and_then(|p| p.incl_vat)),
-        ]);
+        rows.push(ValidationRow {
+            property: "Total Energy Cost (Incl.)",
+            report: to_string_or_default(report.total_energy_cost.and_then(|p| p.incl_vat)),
+            cdr: to_string_or_default(cdr.total_energy_cost.and_then(|p| p.incl_vat)),
+            matches: energy_cost_matches,
+        });
 
-        is_valid &= report
+        let parking_cost_matches = report
             .total_parking_cost
             .zip(cdr.total_parking_cost)
-            .map(|(l, r)| l == r)
-            .unwrap_or(true);
+            .map(|(l, r)| compare_price(l, r, tolerance))
+            .unwrap_or(MatchState::Exact);
+
+        rows.push(ValidationRow {
+            property: "Total Parking Cost (Excl.)",
+            report: to_string_or_default(report.total_parking_cost.map(|p| p.excl_vat)),
+            cdr: to_string_or_default(cdr.total_parking_cost.map(|p| p.excl_vat)),
+            matches: parking_cost_matches,
+        });
 
-        table.retain_rows(|v| !v[1].is_empty() || !v[2].is_empty());
+        rows.push(ValidationRow {
+            property: "Total Parking Cost (Incl.)",
+            report: to_string_or_default(report.total_parking_cost.and_then(|p| p.incl_vat)),
+            cdr: to_string_or_default(cdr.total_parking_cost.and_then(|p| p.incl_vat)),
+            matches: parking_cost_matches,
+        });
 
-        println!("{}", table);
+        rows.retain(|row| !row.report.is_empty() || !row.cdr.is_empty());
+
+        let valid = rows.iter().all(|row| row.matches.is_valid());
+
+        match self.format {
+            OutputFormat::Table => {
+                println!(
+                    "\n{} `{}` with tariff `{}`, using timezone `{}`:",
+                    style("Validating").green().bold(),
+                    style(self.args.cdr_name()).blue(),
+                    style(self.args.tariff_name()).blue(),
+                    style(&report.time_zone).blue(),
+                );
+
+                let mut table = Table::new();
+                table.header(&["Property", "Report", "Cdr", "State"]);
+
+                for row in &rows {
+                    table.row(&[
+                        row.property.to_string(),
+                        row.report.clone(),
+                        row.cdr.clone(),
+                        row.matches.to_string(),
+                    ]);
+                }
 
-        if !is_valid {
-            println!(
-                "Calculation {} all totals in the CDR.\n",
-                style("does not match").red().bold()
-            );
+                println!("{}", table);
+
+                if valid {
+                    println!(
+                        "Calculation {} all totals in the CDR.\n",
+                        style("matches").green().bold()
+                    );
+                } else {
+                    println!(
+                        "Calculation {} all totals in the CDR.\n",
+                        style("does not match").red().bold()
+                    );
+                }
+            }
+            OutputFormat::Json => {
+                let output = serde_json::json!({
+                    "valid": valid,
+                    "properties": rows,
+                });
+
+                println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            }
+            OutputFormat::Csv => {
+                println!("property,report,cdr,matches");
+
+                for row in &rows {
+                    println!(
+                        "{},{},{},{}",
+                        csv_field(row.property),
+                        csv_field(&row.report),
+                        csv_field(&row.cdr),
+                        row.matches,
+                    );
+                }
+            }
+        }
 
+        if !valid {
             exit(1);
-        } else {
-            println!(
-                "Calculation {} all totals in the CDR.\n",
-                style("matches").green().bold()
-            );
         }
 
         Ok(())
@@ -338,12 +622,27 @@ impl Validate {
 pub struct Analyze {
     #[command(flatten)]
     args: TariffArgs,
+    /// The format in which to write the analysis.
+    #[arg(short = 'f', long, value_enum, default_value_t = OutputFormat::default())]
+    format: OutputFormat,
 }
 
 impl Analyze {
     fn run(self) -> Result<()> {
         let (report, _, _) = self.args.load_all()?;
 
+        match self.format {
+            OutputFormat::Table => self.run_table(&report),
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            }
+            OutputFormat::Csv => self.run_csv(&report),
+        }
+
+        Ok(())
+    }
+
+    fn run_table(&self, report: &Report) {
         println!(
             "\n{} `{}` with tariff `{}`, using timezone `{}`:",
             style("Analyzing").green().bold(),
@@ -409,6 +708,112 @@ impl Analyze {
         ]);
 
         println!("{}", table);
+    }
+
+    fn run_csv(&self, report: &Report) {
+        let time_zone: Tz = report.time_zone.parse().expect("invalid time zone");
+
+        println!("period,energy_volume,energy_price,charging_time_volume,charging_time_price,parking_time_volume,parking_time_price,flat_price");
+
+        for period in report.periods.iter() {
+            let start_time = period.start_date_time.with_timezone(&time_zone);
+            let dim = &period.dimensions;
+
+            println!(
+                "{},{},{},{},{},{},{},{}",
+                csv_field(&start_time.to_string()),
+                to_string_or_default(dim.energy.volume),
+                to_string_or_default(dim.energy.price.map(|p| p.price)),
+                to_string_or_default(dim.time.volume),
+                to_string_or_default(dim.time.price.map(|p| p.price)),
+                to_string_or_default(dim.parking_time.volume),
+                to_string_or_default(dim.parking_time.price.map(|p| p.price)),
+                to_string_or_default(dim.flat.price.map(|p| p.price)),
+            );
+        }
+
+        println!(
+            "Total,{},{},{},{},{},{},{}",
+            report.total_energy,
+            to_string_or_default(report.total_energy_cost.map(|p| p.excl_vat)),
+            report.total_time,
+            to_string_or_default(report.total_time_cost.map(|p| p.excl_vat)),
+            report.total_parking_time,
+            to_string_or_default(report.total_parking_cost.map(|p| p.excl_vat)),
+            to_string_or_default(report.total_fixed_cost.map(|p| p.excl_vat)),
+        );
+    }
+}
+
+#[derive(Parser)]
+pub struct Fetch {
+    /// The base URL of the OCPI platform's module endpoints, e.g.
+    /// `https://platform.example.com/ocpi/2.2.1/`.
+    #[arg(short = 'u', long)]
+    base_url: reqwest::Url,
+    /// The token used to authenticate against the platform.
+    #[arg(short = 'k', long)]
+    token: String,
+    /// The OCPI version that should be used for the fetched structures.
+    #[arg(short = 'o', long, value_enum, default_value_t = OcpiVersion::default())]
+    ocpi_version: OcpiVersion,
+    /// Reject malformed or ambiguous input instead of silently tolerating it.
+    #[arg(long)]
+    strict: bool,
+}
+
+impl Fetch {
+    fn run(self) -> Result<()> {
+        let client = HttpOcpiClient::new(self.base_url.clone(), self.token.clone());
+
+        let tariffs = client
+            .fetch_tariffs(self.ocpi_version, self.strict)
+            .map_err(|e| Error::deserialize(&self.base_url, "tariff", e))?;
+
+        let cdrs = client
+            .fetch_cdrs(self.ocpi_version, self.strict)
+            .map_err(|e| Error::deserialize(&self.base_url, "CDR", e))?;
+
+        println!(
+            "\n{} {} CDR(s) against {} tariff(s) from `{}`:",
+            style("Reconciling").green().bold(),
+            cdrs.len(),
+            tariffs.len(),
+            self.base_url,
+        );
+
+        let mut mismatched = 0;
+
+        for cdr in &cdrs {
+            let report = Pricer::new(cdr)
+                .detect_time_zone(true)
+                .with_tariffs(&tariffs)
+                .build_report()
+                .map_err(Error::Internal)?;
+
+            let valid = report
+                .total_cost
+                .map(|p| p == cdr.total_cost)
+                .unwrap_or(true);
+
+            if !valid {
+                mismatched += 1;
+            }
+
+            println!(
+                "  {} - {}",
+                cdr.start_date_time,
+                if valid {
+                    style("matches").green().to_string()
+                } else {
+                    style("does not match").red().to_string()
+                }
+            );
+        }
+
+        if mismatched > 0 {
+            exit(1);
+        }
 
         Ok(())
     }
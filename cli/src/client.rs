@@ -0,0 +1,292 @@
+//! A client for fetching CDRs and tariffs directly from an OCPI platform's `cdrs` and `tariffs`
+//! module endpoints, instead of reading them from a local file.
+
+use std::{io, thread, time::Duration};
+
+use reqwest::{
+    header::{AUTHORIZATION, LINK},
+    Url,
+};
+use serde::de::DeserializeOwned;
+
+use ocpi_tariffs::ocpi::{cdr::Cdr, tariff::OcpiTariff, v211};
+
+use crate::cli::{from_reader_with_version, OcpiVersion};
+
+/// The total number of attempts made for a request, the first one included, before giving up on
+/// a transient failure.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// The delay before the first retry. Each subsequent retry doubles this delay.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+/// A client that can fetch CDRs and tariffs from an OCPI platform.
+pub(crate) trait OcpiClient {
+    fn fetch_cdrs(&self, version: OcpiVersion, strict: bool) -> io::Result<Vec<Cdr>>;
+
+    fn fetch_tariffs(&self, version: OcpiVersion, strict: bool) -> io::Result<Vec<OcpiTariff>>;
+}
+
+/// A blocking [`OcpiClient`] that talks to a platform over HTTP, using token authentication.
+pub(crate) struct HttpOcpiClient {
+    base_url: Url,
+    token: String,
+    client: reqwest::blocking::Client,
+}
+
+impl HttpOcpiClient {
+    pub(crate) fn new(base_url: Url, token: String) -> Self {
+        Self {
+            base_url,
+            token,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Fetch every page of `segment` (`"cdrs"` or `"tariffs"`), following the OCPI `Link` header
+    /// until there is no `rel="next"` page left, and deserialize each element through
+    /// [`from_reader_with_version`] so version detection applies exactly like it does for
+    /// file-based input.
+    fn fetch_all<T0, T1>(
+        &self,
+        segment: &str,
+        version: OcpiVersion,
+        strict: bool,
+    ) -> io::Result<Vec<T0>>
+    where
+        T0: DeserializeOwned + From<T1> + PartialEq,
+        T1: DeserializeOwned,
+    {
+        let mut url = self
+            .base_url
+            .join(segment)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let mut items = Vec::new();
+
+        loop {
+            let response = self.get_with_retry(url.clone())?;
+            let next = next_link(response.headers().get(LINK));
+
+            let body = response
+                .bytes()
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+            let values: Vec<serde_json::Value> = serde_json::from_slice(&body)?;
+
+            for value in values {
+                let bytes = serde_json::to_vec(&value)?;
+                items.push(from_reader_with_version::<_, T0, T1>(
+                    bytes.as_slice(),
+                    version,
+                    strict,
+                )?);
+            }
+
+            match next {
+                Some(next_url) => url = next_url,
+                None => break,
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Perform a GET request, retrying on transient failures (server errors, timeouts, connection
+    /// errors) with an exponentially increasing backoff.
+    fn get_with_retry(&self, url: Url) -> io::Result<reqwest::blocking::Response> {
+        let mut backoff = INITIAL_BACKOFF;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let result = self
+                .client
+                .get(url.clone())
+                .header(AUTHORIZATION, format!("Token {}", self.token))
+                .send();
+
+            let retryable = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(err) => err.is_timeout() || err.is_connect(),
+            };
+
+            match result {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) if retryable && attempt < MAX_ATTEMPTS => {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+                Ok(response) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("OCPI platform returned status {}", response.status()),
+                    ))
+                }
+                Err(err) if retryable && attempt < MAX_ATTEMPTS => {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+                Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err)),
+            }
+        }
+
+        unreachable!("the loop always returns by the last attempt")
+    }
+}
+
+impl OcpiClient for HttpOcpiClient {
+    fn fetch_cdrs(&self, version: OcpiVersion, strict: bool) -> io::Result<Vec<Cdr>> {
+        self.fetch_all::<_, v211::cdr::Cdr>("cdrs", version, strict)
+    }
+
+    fn fetch_tariffs(&self, version: OcpiVersion, strict: bool) -> io::Result<Vec<OcpiTariff>> {
+        self.fetch_all::<_, v211::tariff::OcpiTariff>("tariffs", version, strict)
+    }
+}
+
+/// Parse the `rel="next"` target out of an RFC 5988 `Link` header, if present.
+fn next_link(header: Option<&reqwest::header::HeaderValue>) -> Option<Url> {
+    let header = header?.to_str().ok()?;
+
+    header.split(',').find_map(|part| {
+        let mut segments = part.split(';');
+        let target = segments.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        let is_next = segments.any(|s| s.trim() == r#"rel="next""#);
+
+        is_next.then(|| Url::parse(target).ok()).flatten()
+    })
+}
+
+/// An async counterpart to [`OcpiClient`], for callers that are already running inside a tokio
+/// runtime and want to fetch from several platforms concurrently.
+#[cfg(feature = "async-client")]
+pub(crate) mod asynchronous {
+    use super::*;
+
+    pub(crate) trait AsyncOcpiClient {
+        async fn fetch_cdrs(&self, version: OcpiVersion, strict: bool) -> io::Result<Vec<Cdr>>;
+
+        async fn fetch_tariffs(
+            &self,
+            version: OcpiVersion,
+            strict: bool,
+        ) -> io::Result<Vec<OcpiTariff>>;
+    }
+
+    /// An async [`AsyncOcpiClient`] that talks to a platform over HTTP, using token
+    /// authentication.
+    pub(crate) struct AsyncHttpOcpiClient {
+        base_url: Url,
+        token: String,
+        client: reqwest::Client,
+    }
+
+    impl AsyncHttpOcpiClient {
+        pub(crate) fn new(base_url: Url, token: String) -> Self {
+            Self {
+                base_url,
+                token,
+                client: reqwest::Client::new(),
+            }
+        }
+
+        async fn fetch_all<T0, T1>(
+            &self,
+            segment: &str,
+            version: OcpiVersion,
+            strict: bool,
+        ) -> io::Result<Vec<T0>>
+        where
+            T0: DeserializeOwned + From<T1> + PartialEq,
+            T1: DeserializeOwned,
+        {
+            let mut url = self
+                .base_url
+                .join(segment)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            let mut items = Vec::new();
+
+            loop {
+                let response = self.get_with_retry(url.clone()).await?;
+                let next = next_link(response.headers().get(LINK));
+
+                let body = response
+                    .bytes()
+                    .await
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+                let values: Vec<serde_json::Value> = serde_json::from_slice(&body)?;
+
+                for value in values {
+                    let bytes = serde_json::to_vec(&value)?;
+                    items.push(from_reader_with_version::<_, T0, T1>(
+                        bytes.as_slice(),
+                        version,
+                        strict,
+                    )?);
+                }
+
+                match next {
+                    Some(next_url) => url = next_url,
+                    None => break,
+                }
+            }
+
+            Ok(items)
+        }
+
+        async fn get_with_retry(&self, url: Url) -> io::Result<reqwest::Response> {
+            let mut backoff = INITIAL_BACKOFF;
+
+            for attempt in 1..=MAX_ATTEMPTS {
+                let result = self
+                    .client
+                    .get(url.clone())
+                    .header(AUTHORIZATION, format!("Token {}", self.token))
+                    .send()
+                    .await;
+
+                let retryable = match &result {
+                    Ok(response) => response.status().is_server_error(),
+                    Err(err) => err.is_timeout() || err.is_connect(),
+                };
+
+                match result {
+                    Ok(response) if response.status().is_success() => return Ok(response),
+                    Ok(response) if retryable && attempt < MAX_ATTEMPTS => {
+                        tokio::time::sleep(backoff).await;
+                        backoff *= 2;
+                    }
+                    Ok(response) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!("OCPI platform returned status {}", response.status()),
+                        ))
+                    }
+                    Err(err) if retryable && attempt < MAX_ATTEMPTS => {
+                        tokio::time::sleep(backoff).await;
+                        backoff *= 2;
+                    }
+                    Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err)),
+                }
+            }
+
+            unreachable!("the loop always returns by the last attempt")
+        }
+    }
+
+    impl AsyncOcpiClient for AsyncHttpOcpiClient {
+        async fn fetch_cdrs(&self, version: OcpiVersion, strict: bool) -> io::Result<Vec<Cdr>> {
+            self.fetch_all::<_, v211::cdr::Cdr>("cdrs", version, strict).await
+        }
+
+        async fn fetch_tariffs(
+            &self,
+            version: OcpiVersion,
+            strict: bool,
+        ) -> io::Result<Vec<OcpiTariff>> {
+            self.fetch_all::<_, v211::tariff::OcpiTariff>("tariffs", version, strict)
+                .await
+        }
+    }
+}
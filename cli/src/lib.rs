@@ -1,6 +1,7 @@
 #![doc = include_str!("../README.md")]
 
 mod cli;
+mod client;
 mod error;
 
 type Result<T> = std::result::Result<T, error::Error>;
@@ -0,0 +1,252 @@
+//! Project a tariff into a time-indexed "composite schedule", mirroring the composite schedule
+//! concept from OCPP smart charging: a sorted, gap-free list of piecewise-constant segments
+//! describing which price components apply at each wall-clock instant, without running an actual
+//! charge session through the [`crate::pricer::Pricer`].
+
+use alloc::collections::BTreeSet;
+
+use chrono_tz::Tz;
+
+use crate::{
+    explain::ExplainComponents,
+    ocpi::tariff::{OcpiTariff, TariffDimensionType},
+    restriction::{collect_restrictions, Restriction},
+    tariff::local_date_time,
+    types::time::DateTime,
+};
+
+/// A single piecewise-constant slice of a [`CompositeSchedule`].
+#[derive(Debug)]
+pub struct CompositeSegment {
+    pub start: DateTime,
+    pub end: DateTime,
+    pub components: ExplainComponents,
+    /// Set when at least one restriction that decided which elements apply during this segment
+    /// isn't evaluated by this projection (a volume/duration restriction such as
+    /// `min_kwh`/`max_duration`, which needs session state, a [`Restriction::Holiday`] rule, which
+    /// needs a holiday calendar, or a [`Restriction::Recurring`] rule). The segment's components
+    /// assume such restrictions are unmet.
+    pub conditional: bool,
+}
+
+/// A gap-free, time-ordered projection of `tariff` over `interval`. See [`composite_schedule`].
+#[derive(Debug)]
+pub struct CompositeSchedule {
+    pub segments: Vec<CompositeSegment>,
+}
+
+/// Flatten `tariff` into a [`CompositeSchedule`] covering `interval.0..interval.1`, evaluated in
+/// the `tz` time zone.
+///
+/// Every point at which a tariff element's time/date/weekday restrictions could start or stop
+/// applying (a `start_time`/`end_time` threshold, a `start_date`/`end_date`, or local midnight,
+/// which is when a `day_of_week` restriction changes) becomes a segment edge. Within a segment,
+/// elements are evaluated in OCPI order exactly like [`crate::tariff::Tariff::active_components`]
+/// resolves them for a charge session, except restrictions this projection can't evaluate without
+/// session state are treated as unmet and flag the segment as [`CompositeSegment::conditional`].
+pub fn composite_schedule(
+    tariff: &OcpiTariff,
+    interval: (DateTime, DateTime),
+    tz: Tz,
+) -> CompositeSchedule {
+    let (start, end) = interval;
+
+    if start >= end {
+        return CompositeSchedule {
+            segments: Vec::new(),
+        };
+    }
+
+    let edges = segment_edges(tariff, start, end, tz);
+
+    let mut segments: Vec<CompositeSegment> = Vec::new();
+
+    for window in edges.windows(2) {
+        let [segment_start, segment_end] = window else {
+            unreachable!("windows(2) always yields two-element slices")
+        };
+
+        let local = segment_start.with_timezone(&tz);
+        let (components, conditional) =
+            active_components_at(tariff, local.date_naive(), local.time());
+
+        if let Some(last) = segments.last_mut() {
+            if components_eq(&last.components, &components) && last.conditional == conditional {
+                last.end = *segment_end;
+                continue;
+            }
+        }
+
+        segments.push(CompositeSegment {
+            start: *segment_start,
+            end: *segment_end,
+            components,
+            conditional,
+        });
+    }
+
+    CompositeSchedule { segments }
+}
+
+/// Every segment edge inside `(start, end)`, plus `start` and `end` themselves so the schedule
+/// tiles the whole interval.
+fn segment_edges(tariff: &OcpiTariff, start: DateTime, end: DateTime, tz: Tz) -> Vec<DateTime> {
+    let mut edges = BTreeSet::new();
+    edges.insert(start);
+    edges.insert(end);
+
+    let midnight = chrono::NaiveTime::from_hms_opt(0, 0, 0).expect("midnight is a valid time");
+
+    let mut times = vec![midnight];
+
+    for element in &tariff.elements {
+        let Some(restrictions) = &element.restrictions else {
+            continue;
+        };
+
+        for restriction in collect_restrictions(restrictions) {
+            match restriction {
+                Restriction::StartTime(time) | Restriction::EndTime(time) => times.push(time),
+                Restriction::WrappingTime {
+                    start_time,
+                    end_time,
+                } => {
+                    times.push(start_time);
+                    times.push(end_time);
+                }
+                Restriction::StartDate(date) | Restriction::EndDate(date) => {
+                    if let Some(edge) = local_date_time(date, midnight, tz) {
+                        if edge > start && edge < end {
+                            edges.insert(edge);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut date = start.with_timezone(&tz).date_naive();
+    let end_date = end.with_timezone(&tz).date_naive();
+
+    loop {
+        for &time in &times {
+            if let Some(edge) = local_date_time(date, time, tz) {
+                if edge > start && edge < end {
+                    edges.insert(edge);
+                }
+            }
+        }
+
+        if date >= end_date {
+            break;
+        }
+
+        let Some(next_date) = date.succ_opt() else {
+            break;
+        };
+        date = next_date;
+    }
+
+    edges.into_iter().collect()
+}
+
+/// The components active at `local_date`/`local_time`, resolving elements in OCPI order exactly
+/// like [`crate::tariff::Tariff::active_components`] does for a charge session, and whether doing
+/// so required treating an unevaluable restriction as unmet.
+fn active_components_at(
+    tariff: &OcpiTariff,
+    local_date: chrono::NaiveDate,
+    local_time: chrono::NaiveTime,
+) -> (ExplainComponents, bool) {
+    let mut components = ExplainComponents::default();
+    let mut conditional = false;
+
+    for element in &tariff.elements {
+        let restrictions = element
+            .restrictions
+            .as_ref()
+            .map(collect_restrictions)
+            .unwrap_or_default();
+
+        let mut active = true;
+
+        for restriction in &restrictions {
+            match time_validity(restriction, local_date, local_time) {
+                Some(true) => {}
+                Some(false) => {
+                    active = false;
+                    break;
+                }
+                None => {
+                    conditional = true;
+                    active = false;
+                    break;
+                }
+            }
+        }
+
+        if !active {
+            continue;
+        }
+
+        for component in &element.price_components {
+            match component.component_type {
+                TariffDimensionType::Flat => {
+                    components.flat.get_or_insert(component.price.with_scale(2));
+                }
+                TariffDimensionType::Time => {
+                    components.time.get_or_insert(component.price.with_scale(2));
+                }
+                TariffDimensionType::ParkingTime => {
+                    components
+                        .parking_time
+                        .get_or_insert(component.price.with_scale(2));
+                }
+                TariffDimensionType::Energy => {
+                    components
+                        .energy
+                        .get_or_insert(component.price.with_scale(2));
+                }
+            }
+        }
+
+        if components.flat.is_some()
+            && components.time.is_some()
+            && components.parking_time.is_some()
+            && components.energy.is_some()
+        {
+            break;
+        }
+    }
+
+    (components, conditional)
+}
+
+/// Whether `restriction` holds at `local_date`/`local_time`, or `None` if this projection doesn't
+/// evaluate it: a volume/duration restriction (needs session state), a `Holiday`/`NotHoliday`
+/// rule (needs a [`crate::pricer::HolidayCalendar`]), or a `Recurring` rule.
+fn time_validity(
+    restriction: &Restriction,
+    local_date: chrono::NaiveDate,
+    local_time: chrono::NaiveTime,
+) -> Option<bool> {
+    use chrono::Datelike;
+
+    match restriction {
+        &Restriction::StartTime(start_time) => Some(local_time >= start_time),
+        &Restriction::EndTime(end_time) => Some(local_time < end_time),
+        &Restriction::WrappingTime {
+            start_time,
+            end_time,
+        } => Some(local_time >= start_time || local_time < end_time),
+        &Restriction::StartDate(start_date) => Some(local_date >= start_date),
+        &Restriction::EndDate(end_date) => Some(local_date < end_date),
+        Restriction::DayOfWeek(days) => Some(days.contains(&local_date.weekday())),
+        _ => None,
+    }
+}
+
+fn components_eq(a: &ExplainComponents, b: &ExplainComponents) -> bool {
+    a.flat == b.flat && a.time == b.time && a.parking_time == b.parking_time && a.energy == b.energy
+}
@@ -1,6 +1,6 @@
 use crate::{
     ocpi::v221::tariff::{OcpiTariff, OcpiTariffRestriction, TariffDimensionType},
-    types::money::Money,
+    types::{money::Money, time::DayOfWeek},
 };
 
 #[derive(Debug)]
@@ -75,12 +75,23 @@ pub fn explain_restrictions(restr: &OcpiTariffRestriction) -> Vec<String> {
         ));
     }
 
-    if let Some((start_time, end_time)) = restr.start_time.zip(restr.end_time) {
-        explains.push(format!("between {} and {}", start_time, end_time));
+    let time_phrase = if let Some((start_time, end_time)) = restr.start_time.zip(restr.end_time) {
+        Some(format!("between {} and {}", start_time, end_time))
     } else if let Some(start_time) = restr.start_time {
-        explains.push(format!("after {}", start_time));
+        Some(format!("after {}", start_time))
     } else if let Some(end_time) = restr.end_time {
-        explains.push(format!("before {}", end_time));
+        Some(format!("before {}", end_time))
+    } else {
+        None
+    };
+
+    let day_phrase = day_of_week_phrase(&restr.day_of_week);
+
+    match (day_phrase, time_phrase) {
+        (Some(days), Some(time)) => explains.push(format!("every {} {}", days, time)),
+        (Some(days), None) => explains.push(format!("every {}", days)),
+        (None, Some(time)) => explains.push(time),
+        (None, None) => {}
     }
 
     if let Some((min_duration, max_duration)) = restr.min_duration.zip(restr.max_duration) {
@@ -111,3 +122,76 @@ pub fn explain_restrictions(restr: &OcpiTariffRestriction) -> Vec<String> {
 
     explains
 }
+
+/// Render `days` the way an RRULE would summarize a weekday set: contiguous runs (treating
+/// Monday..Sunday as an ordered cycle, so a run can wrap from Sunday back to Monday) collapse
+/// into a range, with "weekdays" and "weekends" used for the two runs that come up the most, and
+/// non-contiguous groups joined with commas. Returns `None` if `days` is empty.
+fn day_of_week_phrase(days: &[DayOfWeek]) -> Option<String> {
+    if days.is_empty() {
+        return None;
+    }
+
+    let mut present = [false; 7];
+
+    for &day in days {
+        present[weekday_index(day)] = true;
+    }
+
+    if present.iter().all(|&p| p) {
+        return Some("every day".to_string());
+    }
+
+    // Start enumerating just after a gap, so that no contiguous run is split by wrapping past
+    // the end of the array.
+    let start = (0..7).find(|&i| !present[i]).map(|i| (i + 1) % 7)?;
+
+    let mut phrases = Vec::new();
+    let mut offset = 0;
+
+    while offset < 7 {
+        let day = (start + offset) % 7;
+
+        if !present[day] {
+            offset += 1;
+            continue;
+        }
+
+        let mut run_len = 1;
+
+        while run_len < 7 && present[(start + offset + run_len) % 7] {
+            run_len += 1;
+        }
+
+        let first = day;
+        let last = (start + offset + run_len - 1) % 7;
+
+        phrases.push(match (first, last) {
+            (0, 4) => "weekdays".to_string(),
+            (5, 6) => "weekends".to_string(),
+            (first, last) if first == last => weekday_abbr(first).to_string(),
+            (first, last) => format!("{}–{}", weekday_abbr(first), weekday_abbr(last)),
+        });
+
+        offset += run_len;
+    }
+
+    Some(phrases.join(", "))
+}
+
+fn weekday_index(day: DayOfWeek) -> usize {
+    match day {
+        DayOfWeek::Monday => 0,
+        DayOfWeek::Tuesday => 1,
+        DayOfWeek::Wednesday => 2,
+        DayOfWeek::Thursday => 3,
+        DayOfWeek::Friday => 4,
+        DayOfWeek::Saturday => 5,
+        DayOfWeek::Sunday => 6,
+    }
+}
+
+fn weekday_abbr(index: usize) -> &'static str {
+    const ABBRS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    ABBRS[index]
+}
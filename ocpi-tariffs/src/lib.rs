@@ -3,7 +3,9 @@
 //! Functionality to calculate the (sub)totals of a charge session. Use the
 //! [`pricer::Pricer`] to perform the actual calculation.
 
-use std::fmt;
+extern crate alloc;
+
+use core::fmt;
 
 use serde::{Deserialize, Deserializer};
 
@@ -20,6 +22,9 @@ mod tariff;
 /// Module for generating human readable tariffs.
 pub mod explain;
 
+/// Module for projecting a tariff into a time-indexed composite schedule.
+pub mod composite;
+
 /// Module for normalizing tariffs.
 pub mod normalize;
 
@@ -28,7 +33,7 @@ pub mod lint;
 /// OCPI specific numeric types used for calculations, serializing and deserializing.
 pub mod types;
 
-type Result<T> = std::result::Result<T, Error>;
+type Result<T> = core::result::Result<T, Error>;
 
 /// Possible errors when pricing a charge session.
 #[derive(Debug)]
@@ -43,6 +48,8 @@ pub enum Error {
     NoValidTariff,
     /// A numeric overflow occurred during tariff calculation.
     NumericOverflow,
+    /// A division by zero occurred during tariff calculation.
+    DivideByZero,
     /// The CDR location did not contain a time-zone. If time zone detection was enabled and this
     /// error still occurs it means that the country specified in the CDR has multiple time-zones.
     /// Consider explicitly using a time-zone using [`pricer::Pricer::with_time_zone`].
@@ -59,6 +66,15 @@ impl From<rust_decimal::Error> for Error {
     }
 }
 
+impl From<types::number::NumberError> for Error {
+    fn from(error: types::number::NumberError) -> Self {
+        match error {
+            types::number::NumberError::DivideByZero => Self::DivideByZero,
+            types::number::NumberError::Overflow => Self::NumericOverflow,
+        }
+    }
+}
+
 impl std::error::Error for Error {}
 
 impl fmt::Display for Error {
@@ -66,6 +82,7 @@ impl fmt::Display for Error {
         let display = match self {
             Self::NoValidTariff => "No valid tariff has been found in the list of provided tariffs",
             Self::NumericOverflow => "A numeric overflow occurred during tariff calculation",
+            Self::DivideByZero => "A division by zero occurred during tariff calculation",
             Self::TimeZoneMissing => "No time zone could be found in the session information",
             Self::TimeZoneInvalid => "The time zone in the CDR is invalid",
         };
@@ -74,7 +91,7 @@ impl fmt::Display for Error {
     }
 }
 
-fn null_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+fn null_default<'de, D, T>(deserializer: D) -> core::result::Result<T, D::Error>
 where
     T: Default + Deserialize<'de>,
     D: Deserializer<'de>,
@@ -1,15 +1,25 @@
 use std::{collections::HashMap, fmt::Display, iter::once};
 
-use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike};
-use rust_decimal::Decimal;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike, Weekday};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 
-use crate::ocpi::v221::tariff::{OcpiTariff, OcpiTariffRestriction, TariffDimensionType};
+use crate::{
+    ocpi::v221::tariff::{OcpiTariff, OcpiTariffRestriction, TariffDimensionType},
+    types::{electricity::Kwh, number::Number, time::HoursDecimal},
+};
+
+/// Bitmask of the seven weekdays, one bit per [`Weekday::num_days_from_monday`].
+const ALL_DAYS: u8 = 0b111_1111;
+
+fn day_bit(day: Weekday) -> u8 {
+    1 << day.num_days_from_monday()
+}
 
 #[derive(Debug)]
 pub enum Warning {
     DimensionNotExhaustive {
         ty: TariffDimensionType,
-        cases: Vec<()>,
+        cases: Vec<UncoveredCase>,
     },
     ComponentIsRedundant {
         element_index: usize,
@@ -24,7 +34,7 @@ pub enum Warning {
 }
 
 impl Display for Warning {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::UsesDateRestrictions { element_index } => write!(
                 f,
@@ -43,29 +53,112 @@ impl Display for Warning {
                     "Element at `$.elements[{element_index}]` is redundant, consider removing it."
                 )
             }
-            Self::DimensionNotExhaustive { ty, .. } => {
+            Self::DimensionNotExhaustive { ty, cases } => {
                 write!(
                     f,
-                    "Dimension {ty:?} is not exhaustive, consider adding a fallback case."
-                )
+                    "Dimension {ty:?} is not exhaustive, consider adding a fallback case. \
+                     Uncovered, for example: "
+                )?;
+
+                let mut cases = cases.iter();
+
+                if let Some(case) = cases.next() {
+                    write!(f, "{case}")?;
+                }
+
+                for case in cases {
+                    write!(f, "; {case}")?;
+                }
+
+                Ok(())
             }
         }
     }
 }
 
-/// Lint the provided tariff and produce a set of relevant warnings.
-pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
-    let mut warnings = Vec::new();
+/// A minimal example of a session that no price component covers, decoded from a lint witness
+/// into each dimension's native unit.
+#[derive(Debug)]
+pub struct UncoveredCase {
+    pub energy: CaseBound<Kwh>,
+    pub time_of_day: CaseBound<NaiveTime>,
+    pub date: CaseBound<NaiveDate>,
+    pub duration: CaseBound<HoursDecimal>,
+    pub weekday: WeekdaySet,
+}
 
-    let mut energy_elements = Vec::new();
-    let mut flat_elements = Vec::new();
-    let mut time_elements = Vec::new();
-    let mut parking_time_elements = Vec::new();
+impl Display for UncoveredCase {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} kWh charging, {} time of day, {} date, {}, {} session duration",
+            self.energy, self.time_of_day, self.date, self.weekday, self.duration
+        )
+    }
+}
+
+/// A set of weekdays decoded from a lint witness' day-of-week column.
+#[derive(Debug, Clone, Copy)]
+pub struct WeekdaySet(u8);
+
+impl Display for WeekdaySet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        const NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+        if self.0 == ALL_DAYS {
+            return write!(f, "any weekday");
+        }
 
-    // First we expand any element with multiple components into multiple
-    // elements with a single components grouped by component type. This step
-    // also marks any following components of the same type within the same
-    // element redundant.
+        let mut days = (0..7).filter(|bit| self.0 & (1 << bit) != 0).map(|bit| NAMES[bit]);
+
+        if let Some(day) = days.next() {
+            write!(f, "{day}")?;
+        }
+
+        for day in days {
+            write!(f, "/{day}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A value bound decoded from a lint witness: unconstrained sides are rendered as "any" instead
+/// of being dropped, per rustc's `_match.rs` witness convention.
+#[derive(Debug)]
+pub struct CaseBound<T> {
+    pub lower: Option<T>,
+    pub higher: Option<T>,
+}
+
+impl<T: Display> Display for CaseBound<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (&self.lower, &self.higher) {
+            (None, None) => write!(f, "any"),
+            (Some(lower), None) => write!(f, "{lower} and above"),
+            (None, Some(higher)) => write!(f, "below {higher}"),
+            (Some(lower), Some(higher)) => write!(f, "{lower}\u{2013}{higher}"),
+        }
+    }
+}
+
+/// The four single-component element buckets `lint`/`redundant_bounds` both reason about, one
+/// per [`TariffDimensionType`].
+struct GroupedElements {
+    energy: Vec<UnaryElement>,
+    flat: Vec<UnaryElement>,
+    time: Vec<UnaryElement>,
+    parking_time: Vec<UnaryElement>,
+}
+
+/// Expand any element with multiple components into multiple single-component elements grouped
+/// by component type. This step also marks any following components of the same type within the
+/// same element redundant.
+fn group_elements(tariff: &OcpiTariff, warnings: &mut Vec<Warning>) -> GroupedElements {
+    let mut energy = Vec::new();
+    let mut flat = Vec::new();
+    let mut time = Vec::new();
+    let mut parking_time = Vec::new();
 
     for (element_index, element) in tariff.elements.iter().enumerate() {
         if element.price_components.is_empty() {
@@ -80,7 +173,7 @@ pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
         for (component_index, component) in element.price_components.iter().enumerate() {
             match component.component_type {
                 TariffDimensionType::Flat if !has_flat => {
-                    flat_elements.push(UnaryElement {
+                    flat.push(UnaryElement {
                         component_index,
                         element_index,
                         restrictions: element.restrictions.clone(),
@@ -89,7 +182,7 @@ pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
                     has_flat = true;
                 }
                 TariffDimensionType::Time if !has_time => {
-                    time_elements.push(UnaryElement {
+                    time.push(UnaryElement {
                         element_index,
                         component_index,
                         restrictions: element.restrictions.clone(),
@@ -98,7 +191,7 @@ pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
                     has_time = true;
                 }
                 TariffDimensionType::Energy if !has_energy => {
-                    energy_elements.push(UnaryElement {
+                    energy.push(UnaryElement {
                         element_index,
                         component_index,
                         restrictions: element.restrictions.clone(),
@@ -107,7 +200,7 @@ pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
                     has_energy = true;
                 }
                 TariffDimensionType::ParkingTime if !has_parking_time => {
-                    parking_time_elements.push(UnaryElement {
+                    parking_time.push(UnaryElement {
                         element_index,
                         component_index,
                         restrictions: element.restrictions.clone(),
@@ -123,20 +216,31 @@ pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
         }
     }
 
+    GroupedElements {
+        energy,
+        flat,
+        time,
+        parking_time,
+    }
+}
+
+/// Lint the provided tariff and produce a set of relevant warnings.
+pub fn lint(tariff: &OcpiTariff) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    let GroupedElements {
+        mut energy,
+        mut flat,
+        mut time,
+        mut parking_time,
+    } = group_elements(tariff, &mut warnings);
+
     // Now for each component type we attempt to lint the restrictions.
+    lint_restrictions(&mut energy, TariffDimensionType::Energy, &mut warnings);
+    lint_restrictions(&mut flat, TariffDimensionType::Flat, &mut warnings);
+    lint_restrictions(&mut time, TariffDimensionType::Energy, &mut warnings);
     lint_restrictions(
-        &mut energy_elements,
-        TariffDimensionType::Energy,
-        &mut warnings,
-    );
-    lint_restrictions(&mut flat_elements, TariffDimensionType::Flat, &mut warnings);
-    lint_restrictions(
-        &mut time_elements,
-        TariffDimensionType::Energy,
-        &mut warnings,
-    );
-    lint_restrictions(
-        &mut parking_time_elements,
+        &mut parking_time,
         TariffDimensionType::ParkingTime,
         &mut warnings,
     );
@@ -178,77 +282,143 @@ struct UnaryElement {
     restrictions: Option<OcpiTariffRestriction>,
 }
 
-fn lint_restrictions(
-    elements: &mut Vec<UnaryElement>,
-    ty: TariffDimensionType,
-    warnings: &mut Vec<Warning>,
-) {
-    // Define numeric bounds for each restriction range.
+/// The column index of each restriction dimension within the matrices built by [`build_matrix`],
+/// in the `energy`/`time`/`date`/`duration`/`day_of_week` order used throughout this module.
+mod column {
+    pub(super) const ENERGY: usize = 0;
+    pub(super) const TIME: usize = 1;
+    pub(super) const DATE: usize = 2;
+    pub(super) const DURATION: usize = 3;
+    pub(super) const DAY_OF_WEEK: usize = 4;
+}
+
+/// Build the restriction matrix for a single dimension's elements, one pattern per element in
+/// order, without the trailing wildcard pattern [`lint_restrictions`] adds to test exhaustiveness.
+fn build_matrix(elements: &[UnaryElement]) -> Matrix {
+    // Define the domain bounds for each matrix column.
     let bounds = vec![
         // Energy
-        Range::new(Some(Decimal::ZERO), None),
+        Column::Interval(Range::new(Some(Decimal::ZERO), None)),
         // Time in seconds from midnight
-        Range::new(Some(Decimal::ZERO), Some(Decimal::from(60 * 60 * 24))),
+        Column::Interval(Range::new(Some(Decimal::ZERO), Some(Decimal::from(60 * 60 * 24)))),
         // Date in number of days from CE.
-        Range::new(
+        Column::Interval(Range::new(
             Some(Decimal::ZERO),
             Some(Decimal::from(NaiveDate::MAX.num_days_from_ce())),
-        ),
+        )),
         // Duration in milliseconds
-        Range::new(Some(Decimal::ZERO), None),
+        Column::Interval(Range::new(Some(Decimal::ZERO), None)),
+        // Day of week, one bit per day.
+        Column::DaySet(ALL_DAYS),
     ];
 
     let mut matrix = Matrix::new(bounds);
 
-    for element in elements.iter() {
+    for element in elements {
         let Some(restr) = &element.restrictions else {
+            let columns = matrix.bounds.iter().map(Column::top).collect();
+
             matrix.add_pattern(Pattern::new(
-                vec![Range::wildcard(); 4],
+                columns,
                 element.element_index,
                 element.component_index,
             ));
             continue;
         };
 
-        matrix.add_pattern(Pattern::new(
-            vec![
-                Range::new(restr.min_kwh.map(Into::into), restr.max_kwh.map(Into::into)),
-                Range::new(
-                    restr
-                        .start_time
-                        .map(|s| NaiveTime::from(s).num_seconds_from_midnight().into()),
-                    restr
-                        .end_time
-                        .map(|s| NaiveTime::from(s).num_seconds_from_midnight().into()),
-                ),
-                Range::new(
-                    restr
-                        .start_date
-                        .map(|s| NaiveDate::from(s).num_days_from_ce().into()),
-                    restr
-                        .end_date
-                        .map(|s| NaiveDate::from(s).num_days_from_ce().into()),
-                ),
-                Range::new(
-                    restr
-                        .min_duration
-                        .map(|m| Duration::from(m).num_milliseconds().into()),
-                    restr
-                        .max_duration
-                        .map(|m| Duration::from(m).num_milliseconds().into()),
-                ),
-            ],
-            element.element_index,
-            element.component_index,
+        let day_set = if restr.day_of_week.is_empty() {
+            ALL_DAYS
+        } else {
+            restr
+                .day_of_week
+                .iter()
+                .fold(0, |acc, &day| acc | day_bit(Weekday::from(day)))
+        };
+
+        let date = Column::Interval(Range::new(
+            restr
+                .start_date
+                .map(|s| NaiveDate::from(s).num_days_from_ce().into()),
+            restr
+                .end_date
+                .map(|s| NaiveDate::from(s).num_days_from_ce().into()),
         ));
+
+        let duration = Column::Interval(Range::new(
+            restr
+                .min_duration
+                .map(|m| Duration::from(m).num_milliseconds().into()),
+            restr
+                .max_duration
+                .map(|m| Duration::from(m).num_milliseconds().into()),
+        ));
+
+        let energy = Column::Interval(Range::new(
+            restr.min_kwh.map(Into::into),
+            restr.max_kwh.map(Into::into),
+        ));
+
+        // A time-of-day window that wraps past midnight (`start_time` later than `end_time`,
+        // e.g. 22:00-06:00) covers `[start_time, 86_400s) ∪ [0s, end_time)`. Since a single
+        // `Range` can't represent that union, split it into its own pattern per piece; both
+        // pieces share every other column, so the matrix still treats them as one element for
+        // the purposes of usefulness/redundancy.
+        for time in time_of_day_ranges(restr) {
+            matrix.add_pattern(Pattern::new(
+                vec![
+                    energy,
+                    Column::Interval(time),
+                    date,
+                    duration,
+                    Column::DaySet(day_set),
+                ],
+                element.element_index,
+                element.component_index,
+            ));
+        }
     }
 
+    matrix
+}
+
+/// The `[start_time, end_time)` ranges, in seconds from midnight, covered by a restriction's
+/// time-of-day window. Usually a single range, but a window that wraps past midnight (`start_time`
+/// later than `end_time`) is split into the two ranges whose union it represents.
+fn time_of_day_ranges(restr: &OcpiTariffRestriction) -> Vec<Range> {
+    let start = restr
+        .start_time
+        .map(|s| Decimal::from(NaiveTime::from(s).num_seconds_from_midnight()));
+    let end = restr
+        .end_time
+        .map(|s| Decimal::from(NaiveTime::from(s).num_seconds_from_midnight()));
+
+    match (start, end) {
+        (Some(start), Some(end)) if start > end => vec![
+            Range::new(Some(start), Some(Decimal::from(60 * 60 * 24))),
+            Range::new(Some(Decimal::ZERO), Some(end)),
+        ],
+        _ => vec![Range::new(start, end)],
+    }
+}
+
+fn lint_restrictions(
+    elements: &mut Vec<UnaryElement>,
+    ty: TariffDimensionType,
+    warnings: &mut Vec<Warning>,
+) {
+    let mut matrix = build_matrix(elements);
+
     // Add a virtual wildcard element/pattern. If the wildcard is useful or
     // not redundant, it means that this dimension is not exhaustive.
-    matrix.add_pattern(Pattern::wildcard(4));
+    matrix.add_pattern(Pattern::wildcard(&matrix.bounds));
 
     matrix.usefulness();
 
+    // A wrapping time-of-day restriction contributes two patterns (see `time_of_day_ranges`) for
+    // one component, so usefulness is aggregated per `(element_index, component_index)`: the
+    // component is only redundant once every one of its patterns is.
+    let mut component_is_usefull: HashMap<(usize, usize), bool> = HashMap::new();
+
     for pattern in &matrix.patterns[..matrix.patterns.len() - 1] {
         let element_index = pattern
             .element_index
@@ -258,7 +428,15 @@ fn lint_restrictions(
             .component_index
             .expect("pattern should have component index");
 
-        if !pattern.is_usefull {
+        let is_usefull = component_is_usefull
+            .entry((element_index, component_index))
+            .or_insert(false);
+
+        *is_usefull |= pattern.is_usefull;
+    }
+
+    for ((element_index, component_index), is_usefull) in component_is_usefull {
+        if !is_usefull {
             warnings.push(Warning::ComponentIsRedundant {
                 element_index,
                 component_index,
@@ -273,18 +451,171 @@ fn lint_restrictions(
 
     // If the trailing wildcard is useful it means all the elements above are non-exhaustive.
     if last.is_usefull {
-        warnings.push(Warning::DimensionNotExhaustive { ty, cases: vec![] })
+        let cases = last.witness.iter().map(|columns| decode_case(columns)).collect();
+
+        warnings.push(Warning::DimensionNotExhaustive { ty, cases })
     }
 }
 
+/// Which restriction dimension a [`BoundRemoval`] widens away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundKind {
+    Energy,
+    Time,
+    Date,
+    Duration,
+    DayOfWeek,
+}
+
+/// A restriction bound on an element that can be widened to "any" without changing which price
+/// component is selected for any possible charging session, found by [`redundant_bounds`].
+#[derive(Debug)]
+pub struct BoundRemoval {
+    pub element_index: usize,
+    pub bound: BoundKind,
+}
+
+fn bound_kind(column: usize) -> BoundKind {
+    match column {
+        column::ENERGY => BoundKind::Energy,
+        column::TIME => BoundKind::Time,
+        column::DATE => BoundKind::Date,
+        column::DURATION => BoundKind::Duration,
+        _ => BoundKind::DayOfWeek,
+    }
+}
+
+/// Find restriction bounds that are provably redundant: widening them to match every session
+/// doesn't change the outcome, because every session the widened bound would additionally match
+/// is already claimed by an earlier element in the same dimension. Reuses the same usefulness
+/// matrix `lint_restrictions` builds, testing each element's pattern with one column widened to
+/// its [`Column::top`] against only the patterns that precede it.
+///
+/// A single `OcpiTariffRestriction` is shared by every component on an element, so a bound is
+/// only reported once every dimension the element contributes a component to agrees it's
+/// redundant.
+pub(crate) fn redundant_bounds(tariff: &OcpiTariff) -> Vec<BoundRemoval> {
+    let mut discarded_warnings = Vec::new();
+    let GroupedElements {
+        energy,
+        flat,
+        time,
+        parking_time,
+    } = group_elements(tariff, &mut discarded_warnings);
+
+    let mut redundant: HashMap<(usize, usize), bool> = HashMap::new();
+
+    for elements in [&energy, &flat, &time, &parking_time] {
+        if elements.is_empty() {
+            continue;
+        }
+
+        let mut matrix = build_matrix(elements);
+
+        // Elements with a restriction, keyed by their `element_index`, so a pattern can be
+        // skipped when its owning element has no restrictions at all (a wrap-around time window
+        // can make one element own more than one pattern, so we can't zip `elements` and
+        // `matrix.patterns` positionally).
+        let has_restrictions: HashMap<usize, bool> = elements
+            .iter()
+            .map(|element| (element.element_index, element.restrictions.is_some()))
+            .collect();
+
+        for i in 0..matrix.patterns.len() {
+            let element_index = matrix.patterns[i]
+                .element_index
+                .expect("pattern should have element index");
+
+            if !has_restrictions.get(&element_index).copied().unwrap_or(false) {
+                continue;
+            }
+
+            let consider = (0..i).collect::<Vec<_>>();
+
+            for &column in &[
+                column::ENERGY,
+                column::TIME,
+                column::DATE,
+                column::DURATION,
+                column::DAY_OF_WEEK,
+            ] {
+                let top = matrix.bounds[column].top();
+                let original = matrix.patterns[i].columns[column];
+
+                if original == top {
+                    // Already unbounded on this column, nothing to widen.
+                    continue;
+                }
+
+                matrix.patterns[i].columns[column] = top;
+                let is_redundant = matrix.usefulness_rec(0, i, &consider).is_empty();
+                matrix.patterns[i].columns[column] = original;
+
+                let entry = redundant.entry((element_index, column)).or_insert(true);
+
+                *entry &= is_redundant;
+            }
+        }
+    }
+
+    redundant
+        .into_iter()
+        .filter(|&(_, is_redundant)| is_redundant)
+        .map(|((element_index, column), _)| BoundRemoval {
+            element_index,
+            bound: bound_kind(column),
+        })
+        .collect()
+}
+
+/// Decode a witness row, one [`Column`] per column in the
+/// `energy`/`time`/`date`/`duration`/`day_of_week` order used by [`lint_restrictions`], into its
+/// semantic domain.
+fn decode_case(columns: &[Column]) -> UncoveredCase {
+    UncoveredCase {
+        energy: columns[0]
+            .as_interval()
+            .map_bound(|value| Kwh::from(Number::from(value))),
+        time_of_day: columns[1].as_interval().map_bound(seconds_to_time),
+        date: columns[2].as_interval().map_bound(days_to_date),
+        duration: columns[3].as_interval().map_bound(millis_to_duration),
+        weekday: WeekdaySet(columns[4].as_day_set()),
+    }
+}
+
+fn seconds_to_time(seconds: Decimal) -> NaiveTime {
+    let seconds = seconds.to_i64().unwrap_or(0).clamp(0, 24 * 60 * 60 - 1);
+
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds as u32, 0)
+        .unwrap_or_else(|| NaiveTime::from_hms_opt(0, 0, 0).expect("midnight is a valid time"))
+}
+
+fn days_to_date(days: Decimal) -> NaiveDate {
+    let days = days
+        .to_i64()
+        .unwrap_or(0)
+        .clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+
+    NaiveDate::from_num_days_from_ce_opt(days)
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(1, 1, 1).expect("year 1 is a valid date"))
+}
+
+fn millis_to_duration(millis: Decimal) -> HoursDecimal {
+    let millis = millis.to_i64().unwrap_or(0);
+
+    Duration::try_milliseconds(millis)
+        .unwrap_or_else(Duration::zero)
+        .into()
+}
+
 #[derive(Debug)]
 pub struct Matrix {
-    bounds: Vec<Range>,
+    bounds: Vec<Column>,
     patterns: Vec<Pattern>,
 }
 
 impl Matrix {
-    fn new(bounds: Vec<Range>) -> Self {
+    fn new(bounds: Vec<Column>) -> Self {
         Self {
             bounds,
             patterns: Vec::new(),
@@ -321,7 +652,12 @@ impl Matrix {
     /// down and now evaluate constructors for the next column.
     ///
     /// Once we reach the last column we will decide if `pattern` is useful.
-    fn usefulness_rec(&self, column: usize, pattern: usize, consider: &[usize]) -> Vec<Vec<Range>> {
+    fn usefulness_rec(
+        &self,
+        column: usize,
+        pattern: usize,
+        consider: &[usize],
+    ) -> Vec<Vec<Column>> {
         // If we arrived at the last column we should check if any patterns above are still
         // considered. If there are none, this pattern is useful.
         let Some(bounds) = self.bounds.get(column) else {
@@ -335,7 +671,7 @@ impl Matrix {
         let iter = once(&pattern)
             .chain(consider)
             .map(|&i| self.patterns[i].columns[column])
-            .chain(once(Range::wildcard()));
+            .chain(once(bounds.top()));
 
         let mut witnesses = Vec::new();
 
@@ -371,13 +707,24 @@ impl Matrix {
 
 /// Create a list of relevant constructors for a set of patterns.
 ///
-/// An empty list of patterns should produce the constructor set:
-/// `[..]`.
+/// For an [`Column::Interval`] column, an empty list of patterns produces the constructor set
+/// `[..]`, while a list of patterns defined as `[3..4, 6..]` produces the constructor set
+/// `[0..3, 3..4, 4..6, 6..]`.
 ///
-/// A list of patterns defined as `[3..4, 6..]` should produce the constructor set:
-/// `[0..3, 3..4, 4..6, 6..]`
-///
-fn constructors(bounds: Range, ranges: impl Iterator<Item = Range>) -> Vec<Range> {
+/// For a [`Column::DaySet`] column, the constructor set is the seven singleton weekdays that
+/// appear in any pattern, plus the complement of their union (the remaining, otherwise
+/// unmentioned, weekdays) if non-empty.
+fn constructors(bounds: Column, columns: impl Iterator<Item = Column>) -> Vec<Column> {
+    match bounds {
+        Column::Interval(bounds) => interval_constructors(
+            bounds,
+            columns.map(|column| column.as_interval()),
+        ),
+        Column::DaySet(_) => day_constructors(columns.map(|column| column.as_day_set())),
+    }
+}
+
+fn interval_constructors(bounds: Range, ranges: impl Iterator<Item = Range>) -> Vec<Column> {
     #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
     enum Point {
         NegInf,
@@ -420,7 +767,7 @@ fn constructors(bounds: Range, ranges: impl Iterator<Item = Range>) -> Vec<Range
             (None, Some(higher)) if Some(higher) == bounds.lower => {}
             (Some(lower), None) if Some(lower) == bounds.higher => {}
             _ => {
-                ranges.push(Range::new(lower, higher));
+                ranges.push(Column::Interval(Range::new(lower, higher)));
             }
         }
 
@@ -430,19 +777,37 @@ fn constructors(bounds: Range, ranges: impl Iterator<Item = Range>) -> Vec<Range
     ranges
 }
 
+fn day_constructors(sets: impl Iterator<Item = u8>) -> Vec<Column> {
+    let union = sets.fold(0u8, |acc, set| acc | set);
+
+    let mut constructors: Vec<Column> = (0..7)
+        .map(|bit| 1u8 << bit)
+        .filter(|day| union & day != 0)
+        .map(Column::DaySet)
+        .collect();
+
+    let complement = !union & ALL_DAYS;
+
+    if complement != 0 {
+        constructors.push(Column::DaySet(complement));
+    }
+
+    constructors
+}
+
 #[derive(Debug)]
 struct Pattern {
-    columns: Vec<Range>,
+    columns: Vec<Column>,
     is_usefull: bool,
-    witness: Vec<Vec<Range>>,
+    witness: Vec<Vec<Column>>,
     element_index: Option<usize>,
     component_index: Option<usize>,
 }
 
 impl Pattern {
-    fn wildcard(width: usize) -> Self {
+    fn wildcard(bounds: &[Column]) -> Self {
         Self {
-            columns: vec![Range::wildcard(); width],
+            columns: bounds.iter().map(Column::top).collect(),
             is_usefull: false,
             witness: Vec::new(),
             element_index: None,
@@ -450,7 +815,7 @@ impl Pattern {
         }
     }
 
-    fn new(columns: Vec<Range>, element_index: usize, component_index: usize) -> Self {
+    fn new(columns: Vec<Column>, element_index: usize, component_index: usize) -> Self {
         Self {
             columns,
             is_usefull: false,
@@ -461,13 +826,62 @@ impl Pattern {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A single column of the lint matrix: either a numeric interval, used for the energy, time,
+/// date and duration dimensions, or a set of weekdays, used for the day-of-week dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Interval(Range),
+    DaySet(u8),
+}
+
+impl Column {
+    /// The top element of this column's domain, i.e. the value an absent restriction stands for.
+    fn top(&self) -> Self {
+        match self {
+            Self::Interval(_) => Self::Interval(Range::wildcard()),
+            Self::DaySet(_) => Self::DaySet(ALL_DAYS),
+        }
+    }
+
+    fn as_interval(self) -> Range {
+        match self {
+            Self::Interval(range) => range,
+            Self::DaySet(_) => unreachable!("day-of-week column decoded as an interval"),
+        }
+    }
+
+    fn as_day_set(self) -> u8 {
+        match self {
+            Self::DaySet(days) => days,
+            Self::Interval(_) => unreachable!("interval column decoded as a day set"),
+        }
+    }
+
+    fn contains(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Interval(lhs), Self::Interval(rhs)) => lhs.contains(rhs),
+            (Self::DaySet(lhs), Self::DaySet(rhs)) => lhs & rhs == *rhs,
+            _ => unreachable!("columns of different kinds compared"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Range {
     pub lower: Option<Decimal>,
     pub higher: Option<Decimal>,
 }
 
 impl Range {
+    /// Map this range's bounds through `f`, turning a `Decimal`-based [`Range`] into a
+    /// [`CaseBound`] in a dimension's native unit. An unbounded side stays unbounded.
+    fn map_bound<T>(self, f: impl Fn(Decimal) -> T) -> CaseBound<T> {
+        CaseBound {
+            lower: self.lower.map(&f),
+            higher: self.higher.map(&f),
+        }
+    }
+
     fn contains(&self, other: &Self) -> bool {
         match (self.lower, other.lower) {
             (None, _) => {}
@@ -1,5 +1,5 @@
 use crate::{
-    lint::{lint, Warning},
+    lint::{lint, redundant_bounds, BoundKind, Warning},
     ocpi::v221::tariff::OcpiTariff,
 };
 
@@ -23,6 +23,34 @@ pub fn normalize(tariff: &mut OcpiTariff) {
     remove_components.sort_unstable();
     remove_elements.sort_unstable();
 
+    // Clear bounds the lint matrix proved redundant before removing elements/components, so the
+    // indices `redundant_bounds` reported still line up with `tariff.elements`.
+    for removal in redundant_bounds(tariff) {
+        let Some(restrictions) = &mut tariff.elements[removal.element_index].restrictions else {
+            continue;
+        };
+
+        match removal.bound {
+            BoundKind::Energy => {
+                restrictions.min_kwh = None;
+                restrictions.max_kwh = None;
+            }
+            BoundKind::Time => {
+                restrictions.start_time = None;
+                restrictions.end_time = None;
+            }
+            BoundKind::Date => {
+                restrictions.start_date = None;
+                restrictions.end_date = None;
+            }
+            BoundKind::Duration => {
+                restrictions.min_duration = None;
+                restrictions.max_duration = None;
+            }
+            BoundKind::DayOfWeek => restrictions.day_of_week.clear(),
+        }
+    }
+
     // Remove them in sorted reverse order for the indices to stay intact.
     for &(el, comp) in remove_components.iter().rev() {
         tariff.elements[el].price_components.remove(comp);
@@ -147,6 +147,7 @@ impl From<OcpiTariffRestriction> for v221::tariff::OcpiTariffRestriction {
             max_current: None,
             min_current: None,
             reservation: None,
+            rrule: None,
         }
     }
 }
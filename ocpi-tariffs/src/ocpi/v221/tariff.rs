@@ -161,6 +161,16 @@ pub struct OcpiTariffRestriction {
 
     /// Whether this tariff applies for reservation.
     pub reservation: Option<ReservationRestrictionType>,
+
+    /// An RFC 5545 `RRULE` value, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TU`, anchored to
+    /// `start_date`.
+    ///
+    /// OCPI has no recurring-restriction field, so this is a non-standard extension for tariffs
+    /// that need validity windows a plain `day_of_week`/`start_date`/`end_date` can't express,
+    /// such as "first Monday of the month" or "every other week". Absent from the spec, so it
+    /// defaults to `None` and existing tariff JSON keeps parsing unchanged.
+    #[serde(default)]
+    pub rrule: Option<String>,
 }
 
 /// The type of reservation a tariff applies to.
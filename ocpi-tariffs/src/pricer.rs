@@ -1,11 +1,15 @@
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+
 use crate::{
     ocpi::{
         cdr::Cdr,
         tariff::{CompatibilityVat, OcpiTariff},
     },
     session::{ChargePeriod, ChargeSession, PeriodData},
-    tariff::{PriceComponent, PriceComponents, Tariff},
+    tariff::{next_offset_transition, PriceComponent, PriceComponents, Tariff},
     types::{
+        currency::minor_units,
         electricity::Kwh,
         money::{Money, Price},
         number::Number,
@@ -14,10 +18,79 @@ use crate::{
     Error, Result,
 };
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc, Weekday};
 use chrono_tz::Tz;
 use serde::Serialize;
 
+/// A set of calendar dates that should be treated as holidays when evaluating tariff
+/// restrictions, such as [`crate::restriction::Restriction::Holiday`] or a `DayOfWeek`
+/// restriction.
+///
+/// OCPI itself has no notion of a holiday, so the calendar is supplied by the caller rather
+/// than inferred from the tariff or CDR, letting the same tariff be priced against different
+/// regional holiday sets.
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    /// Each holiday date, optionally mapped to its own substitute weekday, overriding
+    /// `treat_as_weekday` for that date only.
+    dates: BTreeMap<NaiveDate, Option<Weekday>>,
+    treat_as_weekday: Option<Weekday>,
+}
+
+impl HolidayCalendar {
+    /// Create an empty calendar, no date is considered a holiday.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the provided dates as holidays, evaluated against `treat_as_weekday` (or their actual
+    /// weekday, if none is set) unless overridden per-date by [`Self::with_date_substitute`].
+    #[must_use]
+    pub fn with_dates(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.dates.extend(dates.into_iter().map(|date| (date, None)));
+
+        self
+    }
+
+    /// Mark `date` as a holiday that should be evaluated as `weekday` instead of its actual
+    /// weekday. Takes precedence over [`Self::treat_as_weekday`] for this date, so e.g. a
+    /// regional holiday that should be priced as a Saturday can be mixed into a calendar whose
+    /// other holidays fall back to Sunday rates.
+    #[must_use]
+    pub fn with_date_substitute(mut self, date: NaiveDate, weekday: Weekday) -> Self {
+        self.dates.insert(date, Some(weekday));
+
+        self
+    }
+
+    /// When set, a `DayOfWeek` restriction will test a holiday date against `weekday` instead of
+    /// its actual weekday (commonly [`Weekday::Sun`]), unless that date has its own substitute
+    /// from [`Self::with_date_substitute`].
+    #[must_use]
+    pub fn treat_as_weekday(mut self, weekday: Weekday) -> Self {
+        self.treat_as_weekday = Some(weekday);
+
+        self
+    }
+
+    pub(crate) fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.dates.contains_key(&date)
+    }
+
+    /// Remap `date`'s `actual` weekday to its configured substitute weekday if `date` is a
+    /// holiday, otherwise return `actual` unchanged. A per-date substitute from
+    /// [`Self::with_date_substitute`] takes precedence over the calendar-wide
+    /// [`Self::treat_as_weekday`].
+    pub(crate) fn weekday(&self, date: NaiveDate, actual: Weekday) -> Weekday {
+        match self.dates.get(&date) {
+            Some(Some(substitute)) => *substitute,
+            Some(None) => self.treat_as_weekday.unwrap_or(actual),
+            None => actual,
+        }
+    }
+}
+
 /// Pricer that encapsulates a single charge-session and a list of tariffs.
 /// To run the pricer call `build_report`. The resulting report contains the totals, subtotals and a breakdown of the
 /// calculation.
@@ -43,6 +116,25 @@ pub struct Pricer<'a> {
     tariffs: Option<Vec<&'a OcpiTariff>>,
     time_zone: Option<Tz>,
     detect_time_zone: bool,
+    holiday_calendar: HolidayCalendar,
+    scale: Option<u32>,
+    checked_arithmetic: bool,
+    tariff_selection: TariffSelection,
+}
+
+/// How to pick among several tariffs that are all active at a session's start time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TariffSelection {
+    /// Use the first active tariff, in the order the tariffs were provided. The original, and
+    /// still default, behaviour.
+    #[default]
+    FirstActive,
+    /// Price the session against every active tariff in the same currency as the [`Cdr`] and keep
+    /// the one with the lowest [`Report::total_cost`] (preferring `incl_vat`, falling back to
+    /// `excl_vat` when VAT is unknown). Ties are broken by the lowest tariff index.
+    Cheapest,
+    /// Like [`Self::Cheapest`], but keeps the highest-cost tariff instead, for worst-case quoting.
+    MostExpensive,
 }
 
 impl<'a> Pricer<'a> {
@@ -54,6 +146,10 @@ impl<'a> Pricer<'a> {
             time_zone: None,
             detect_time_zone: false,
             tariffs: None,
+            holiday_calendar: HolidayCalendar::new(),
+            scale: None,
+            checked_arithmetic: false,
+            tariff_selection: TariffSelection::FirstActive,
         }
     }
 
@@ -65,6 +161,16 @@ impl<'a> Pricer<'a> {
         self
     }
 
+    /// Supply a [`HolidayCalendar`] used to evaluate holiday-aware restrictions.
+    ///
+    /// Without a calendar no date is considered a holiday.
+    #[must_use]
+    pub fn with_holiday_calendar(mut self, calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = calendar;
+
+        self
+    }
+
     /// Directly specify a time zone to use for the calculation. This overrides any time zones in
     /// the session or any detected time zones if [`Self::detect_time_zone`] is set to true.
     #[must_use]
@@ -85,9 +191,42 @@ impl<'a> Pricer<'a> {
         self
     }
 
-    /// Attempt to apply the first applicable tariff to the charge session and build a report
-    /// containing the results.
-    #[allow(clippy::too_many_lines)]
+    /// Override the rounding scale (number of decimals) used for the calculated costs in the
+    /// [`Report`], instead of deriving it from the CDR's currency.
+    #[must_use]
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = Some(scale);
+
+        self
+    }
+
+    /// Opt into checked (rather than saturating) arithmetic when applying a tariff element's
+    /// step-size rounding.
+    ///
+    /// By default, an out-of-range `ceil`-then-multiply step-size volume (e.g. a corrupt CDR
+    /// reporting an astronomically large energy volume) is silently clamped into the largest
+    /// representable value, same as the rest of this module's running totals. Enabling this
+    /// instead returns [`Error::NumericOverflow`] from [`Self::build_report`], so a priced
+    /// [`Report`] is either exactly correct or an explicit error.
+    #[must_use]
+    pub fn with_checked_arithmetic(mut self, checked: bool) -> Self {
+        self.checked_arithmetic = checked;
+
+        self
+    }
+
+    /// Choose how to pick among several tariffs that are all active at the session's start time.
+    /// Defaults to [`TariffSelection::FirstActive`].
+    #[must_use]
+    pub fn with_tariff_selection(mut self, selection: TariffSelection) -> Self {
+        self.tariff_selection = selection;
+
+        self
+    }
+
+    /// Attempt to apply a tariff to the charge session and build a report containing the
+    /// results. Which tariff is chosen, when more than one is active, is governed by
+    /// [`Self::with_tariff_selection`].
     pub fn build_report(self) -> Result<Report> {
         let cdr_tz = self.cdr.cdr_location.time_zone.as_ref();
 
@@ -101,66 +240,213 @@ impl<'a> Pricer<'a> {
             return Err(Error::TimeZoneMissing);
         };
 
-        let cdr = ChargeSession::new(self.cdr, time_zone);
+        let currency = self.cdr.currency.clone();
+        let scale = self.scale;
+        let checked_arithmetic = self.checked_arithmetic;
+        let tariff_selection = self.tariff_selection;
 
-        let active = if let Some(tariffs) = self.tariffs {
-            Self::first_active_tariff(tariffs, cdr.start_date_time)
-        } else if !self.cdr.tariffs.is_empty() {
-            Self::first_active_tariff(&self.cdr.tariffs, cdr.start_date_time)
-        } else {
-            None
-        };
+        let cdr = ChargeSession::new(self.cdr, time_zone, Rc::new(self.holiday_calendar));
+
+        match tariff_selection {
+            TariffSelection::FirstActive => {
+                let active = if let Some(tariffs) = self.tariffs {
+                    Self::first_active_tariff(tariffs, cdr.start_date_time)
+                } else if !self.cdr.tariffs.is_empty() {
+                    Self::first_active_tariff(&self.cdr.tariffs, cdr.start_date_time)
+                } else {
+                    None
+                };
+
+                let (tariff_index, tariff) = active.ok_or(Error::NoValidTariff)?;
+
+                Self::price_tariff(
+                    &cdr,
+                    tariff_index,
+                    tariff,
+                    time_zone,
+                    &currency,
+                    scale,
+                    checked_arithmetic,
+                )
+            }
+            TariffSelection::Cheapest | TariffSelection::MostExpensive => {
+                let candidates = if let Some(tariffs) = &self.tariffs {
+                    Self::candidate_tariffs(tariffs.iter().copied(), cdr.start_date_time, &currency)
+                } else {
+                    Self::candidate_tariffs(&self.cdr.tariffs, cdr.start_date_time, &currency)
+                };
+
+                let mut best: Option<Report> = None;
+
+                for (tariff_index, tariff) in candidates {
+                    let report = Self::price_tariff(
+                        &cdr,
+                        tariff_index,
+                        tariff,
+                        time_zone,
+                        &currency,
+                        scale,
+                        checked_arithmetic,
+                    )?;
+
+                    let keep = match &best {
+                        None => true,
+                        Some(current) => {
+                            let candidate_value = Self::cost_comparison_value(report.total_cost);
+                            let current_value = Self::cost_comparison_value(current.total_cost);
+
+                            match tariff_selection {
+                                TariffSelection::Cheapest => candidate_value < current_value,
+                                TariffSelection::MostExpensive => candidate_value > current_value,
+                                TariffSelection::FirstActive => false,
+                            }
+                        }
+                    };
+
+                    if keep {
+                        best = Some(report);
+                    }
+                }
 
-        let (tariff_index, tariff) = active.ok_or(Error::NoValidTariff)?;
+                best.ok_or(Error::NoValidTariff)
+            }
+        }
+    }
 
+    /// Every tariff in `iter` that is both active at `start_date_time` and in the same `currency`
+    /// as the [`Cdr`], paired with its index in the original list (so [`Report::tariff_index`]
+    /// stays meaningful regardless of how many candidates were filtered out).
+    fn candidate_tariffs<'b>(
+        iter: impl IntoIterator<Item = &'b OcpiTariff>,
+        start_date_time: OcpiDateTime,
+        currency: &str,
+    ) -> Vec<(usize, Tariff)> {
+        iter.into_iter()
+            .enumerate()
+            .filter(|(_, ocpi_tariff)| ocpi_tariff.currency == currency)
+            .map(|(index, ocpi_tariff)| (index, Tariff::new(ocpi_tariff)))
+            .filter(|(_, tariff)| tariff.is_active(start_date_time))
+            .collect()
+    }
+
+    /// The value used to compare tariffs under [`TariffSelection::Cheapest`]/
+    /// [`TariffSelection::MostExpensive`]: `total_cost`'s `incl_vat`, falling back to `excl_vat`
+    /// when VAT is unknown, or zero if no dimension had a cost at all.
+    fn cost_comparison_value(total_cost: Option<Price>) -> Money {
+        total_cost.map_or(Money::zero(), |price| price.incl_vat.unwrap_or(price.excl_vat))
+    }
+
+    /// Price `cdr` against a single `tariff` and build the resulting report.
+    #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
+    fn price_tariff(
+        cdr: &ChargeSession,
+        tariff_index: usize,
+        tariff: Tariff,
+        time_zone: Tz,
+        currency: &str,
+        scale_override: Option<u32>,
+        checked_arithmetic: bool,
+    ) -> Result<Report> {
         let mut periods = Vec::new();
         let mut step_size = StepSize::new();
 
         let mut total_energy = Kwh::zero();
         let mut total_charging_time = HoursDecimal::zero();
         let mut total_parking_time = HoursDecimal::zero();
+        let mut total_reservation_time = HoursDecimal::zero();
 
         let mut has_flat_fee = false;
-
-        for (index, period) in cdr.periods.iter().enumerate() {
-            let mut components = tariff.active_components(period);
-
-            if components.flat.is_some() {
-                if has_flat_fee {
-                    components.flat = None;
-                } else {
-                    has_flat_fee = true;
+        let mut exceeded_max_duration = false;
+
+        for period in cdr.periods.iter() {
+            // A period can need more than one split, e.g. it crosses a `max_duration` restriction
+            // and also straddles a tariff element's validity change: keep splitting off the
+            // leading segment until what's left no longer straddles a boundary.
+            let mut remainder = period.clone();
+
+            loop {
+                let mut warnings = PeriodWarnings::new();
+                warnings.dst_transition_in_period =
+                    remainder.start_instant.utc_offset() != remainder.end_instant.utc_offset();
+                let components = tariff.active_components(&remainder, &mut warnings);
+
+                match Self::next_split(&tariff, &remainder) {
+                    Some(PeriodSplit::MaxDuration(max_duration)) => {
+                        exceeded_max_duration = true;
+                        warnings.exceeded_max_duration = true;
+
+                        let (segment, rest) = remainder.split_at(max_duration);
+
+                        Self::price_period(
+                            &segment,
+                            components,
+                            warnings,
+                            &mut has_flat_fee,
+                            &mut periods,
+                            &mut step_size,
+                            &mut total_charging_time,
+                            &mut total_energy,
+                            &mut total_parking_time,
+                            &mut total_reservation_time,
+                        );
+
+                        remainder = rest;
+                    }
+                    Some(PeriodSplit::DateTime(boundary)) => {
+                        let (segment, rest) = remainder.split_at_date_time(boundary);
+
+                        Self::price_period(
+                            &segment,
+                            components,
+                            warnings,
+                            &mut has_flat_fee,
+                            &mut periods,
+                            &mut step_size,
+                            &mut total_charging_time,
+                            &mut total_energy,
+                            &mut total_parking_time,
+                            &mut total_reservation_time,
+                        );
+
+                        remainder = rest;
+                    }
+                    None => {
+                        Self::price_period(
+                            &remainder,
+                            components,
+                            warnings,
+                            &mut has_flat_fee,
+                            &mut periods,
+                            &mut step_size,
+                            &mut total_charging_time,
+                            &mut total_energy,
+                            &mut total_parking_time,
+                            &mut total_reservation_time,
+                        );
+
+                        break;
+                    }
                 }
             }
-
-            step_size.update(index, &components, period);
-
-            let dimensions = Dimensions::new(&components, &period.period_data);
-
-            total_charging_time = total_charging_time
-                .saturating_add(dimensions.time.volume.unwrap_or_else(HoursDecimal::zero));
-
-            total_energy =
-                total_energy.saturating_add(dimensions.energy.volume.unwrap_or_else(Kwh::zero));
-
-            total_parking_time = total_parking_time.saturating_add(
-                dimensions
-                    .parking_time
-                    .volume
-                    .unwrap_or_else(HoursDecimal::zero),
-            );
-
-            periods.push(PeriodReport::new(period, dimensions));
         }
 
-        let billed_charging_time = step_size.apply_time(&mut periods, total_charging_time)?;
-        let billed_energy = step_size.apply_energy(&mut periods, total_energy);
-        let billed_parking_time = step_size.apply_parking_time(&mut periods, total_parking_time)?;
+        let billed_charging_time =
+            step_size.apply_time(&mut periods, total_charging_time, checked_arithmetic)?;
+        let billed_energy =
+            step_size.apply_energy(&mut periods, total_energy, checked_arithmetic)?;
+        let billed_parking_time =
+            step_size.apply_parking_time(&mut periods, total_parking_time, checked_arithmetic)?;
+        let billed_reservation_time = step_size.apply_reservation_time(
+            &mut periods,
+            total_reservation_time,
+            checked_arithmetic,
+        )?;
 
         let mut total_energy_cost: Option<Price> = None;
         let mut total_time_cost: Option<Price> = None;
         let mut total_parking_cost: Option<Price> = None;
         let mut total_fixed_cost: Option<Price> = None;
+        let mut total_reservation_cost: Option<Price> = None;
 
         for period in &periods {
             let dimensions = &period.dimensions;
@@ -200,6 +486,16 @@ impl<'a> Pricer<'a> {
                         .saturating_add(period.unwrap_or_default()),
                 ),
             };
+
+            total_reservation_cost = match (total_reservation_cost, dimensions.reservation.cost())
+            {
+                (None, None) => None,
+                (total, period) => Some(
+                    total
+                        .unwrap_or_default()
+                        .saturating_add(period.unwrap_or_default()),
+                ),
+            };
         }
 
         let total_time = if let Some(first) = periods.first() {
@@ -217,6 +513,7 @@ impl<'a> Pricer<'a> {
             total_parking_cost,
             total_fixed_cost,
             total_energy_cost,
+            total_reservation_cost,
         ]
         .into_iter()
         .fold(None, |accum: Option<Price>, next| match (accum, next) {
@@ -228,12 +525,48 @@ impl<'a> Pricer<'a> {
             ),
         });
 
+        let uncapped_total_cost = total_cost;
+
+        // A session with no matched price components at all (every dimension cost `None`) still
+        // owes at least `min_price` if the tariff defines one; treat it as a zero total for the
+        // purpose of the floor rather than leaving it unbilled.
+        let total_cost = if total_cost.is_none() && tariff.min_price.is_some() {
+            Some(Price::zero().clamp(tariff.min_price, tariff.max_price))
+        } else {
+            total_cost.map(|cost| cost.clamp(tariff.min_price, tariff.max_price))
+        };
+
+        let price_cap = match (uncapped_total_cost, total_cost) {
+            (Some(uncapped), Some(capped)) if capped.excl_vat > uncapped.excl_vat => {
+                Some(PriceCap::Min)
+            }
+            (Some(uncapped), Some(capped)) if capped.excl_vat < uncapped.excl_vat => {
+                Some(PriceCap::Max)
+            }
+            (None, Some(_)) => Some(PriceCap::Min),
+            _ => None,
+        };
+
+        // Per-period costs keep full `rust_decimal` precision throughout, to avoid accumulating
+        // rounding error; only the final session-wide totals are rounded, to the currency's own
+        // minor unit rather than OCPI's fixed 4 decimals.
+        let scale = scale_override.unwrap_or_else(|| minor_units(currency));
+        let total_cost = total_cost.map(|cost| cost.with_scale(scale));
+        let uncapped_total_cost = uncapped_total_cost.map(|cost| cost.with_scale(scale));
+        let total_time_cost = total_time_cost.map(|cost| cost.with_scale(scale));
+        let total_parking_cost = total_parking_cost.map(|cost| cost.with_scale(scale));
+        let total_energy_cost = total_energy_cost.map(|cost| cost.with_scale(scale));
+        let total_fixed_cost = total_fixed_cost.map(|cost| cost.with_scale(scale));
+        let total_reservation_cost = total_reservation_cost.map(|cost| cost.with_scale(scale));
+
         let report = Report {
             periods,
             tariff_index,
             tariff_id: tariff.id,
             time_zone: time_zone.to_string(),
+            currency: currency.to_string(),
             total_cost,
+            uncapped_total_cost,
             total_time_cost,
             total_charging_time,
             total_time,
@@ -245,12 +578,99 @@ impl<'a> Pricer<'a> {
             billed_parking_time,
             billed_energy,
             billed_charging_time,
-            total_reservation_cost: None,
+            total_reservation_cost,
+            total_reservation_time,
+            billed_reservation_time,
+            exceeded_max_duration,
+            price_cap,
         };
 
         Ok(report)
     }
 
+    /// The earliest point, if any, at which `period` must be split before it can be priced as a
+    /// single segment: either a `max_duration` restriction being crossed, or a tariff element's
+    /// validity changing partway through (e.g. its restrictions' time window starts or ends, or
+    /// local midnight passes and a date-based restriction takes effect). When both would apply,
+    /// the one that comes first wins.
+    fn next_split(tariff: &Tariff, period: &ChargePeriod) -> Option<PeriodSplit> {
+        let max_duration = tariff.max_duration_crossed(period);
+        let max_duration_date_time = max_duration.map(|boundary| {
+            period.start_instant.date_time + (boundary - period.start_instant.total_duration)
+        });
+        let restriction_boundary = tariff.next_restriction_boundary(period);
+        let offset_transition = next_offset_transition(period);
+
+        let earliest_date_time_boundary = [restriction_boundary, offset_transition]
+            .into_iter()
+            .flatten()
+            .min();
+
+        match (
+            max_duration.zip(max_duration_date_time),
+            earliest_date_time_boundary,
+        ) {
+            (Some((_, max_duration_date_time)), Some(boundary))
+                if boundary < max_duration_date_time =>
+            {
+                Some(PeriodSplit::DateTime(boundary))
+            }
+            (Some((max_duration, _)), _) => Some(PeriodSplit::MaxDuration(max_duration)),
+            (None, Some(boundary)) => Some(PeriodSplit::DateTime(boundary)),
+            (None, None) => None,
+        }
+    }
+
+    /// Price a single (possibly split-off) period: apply the once-per-session flat fee, record it
+    /// for step-size rounding, fold its volumes into the running totals and push its report.
+    #[allow(clippy::too_many_arguments)]
+    fn price_period(
+        period: &ChargePeriod,
+        mut components: PriceComponents,
+        warnings: PeriodWarnings,
+        has_flat_fee: &mut bool,
+        periods: &mut Vec<PeriodReport>,
+        step_size: &mut StepSize,
+        total_charging_time: &mut HoursDecimal,
+        total_energy: &mut Kwh,
+        total_parking_time: &mut HoursDecimal,
+        total_reservation_time: &mut HoursDecimal,
+    ) {
+        if components.flat.is_some() {
+            if *has_flat_fee {
+                components.flat = None;
+            } else {
+                *has_flat_fee = true;
+            }
+        }
+
+        step_size.update(periods.len(), &components, period);
+
+        let dimensions = Dimensions::new(&components, &period.period_data);
+
+        *total_charging_time = total_charging_time
+            .saturating_add(dimensions.time.volume.unwrap_or_else(HoursDecimal::zero));
+
+        *total_energy =
+            total_energy.saturating_add(dimensions.energy.volume.unwrap_or_else(Kwh::zero));
+
+        *total_parking_time = total_parking_time.saturating_add(
+            dimensions
+                .parking_time
+                .volume
+                .unwrap_or_else(HoursDecimal::zero),
+        );
+
+        *total_reservation_time = total_reservation_time.saturating_add(
+            dimensions
+                .reservation
+                .volume
+                .unwrap_or_else(HoursDecimal::zero),
+        );
+
+        periods.push(PeriodReport::new(period, dimensions, warnings));
+    }
+
     fn first_active_tariff<'b>(
         iter: impl IntoIterator<Item = &'b OcpiTariff>,
         start_date_time: OcpiDateTime,
@@ -262,10 +682,21 @@ impl<'a> Pricer<'a> {
     }
 }
 
+/// The kind of boundary a [`ChargePeriod`] is being split at, and which of its two split methods
+/// applies: a crossed `max_duration` restriction only apportions duration, while a restriction
+/// validity change apportions every volume.
+enum PeriodSplit {
+    MaxDuration(Duration),
+    /// A restriction-validity change or a timezone offset (DST) transition, both of which split a
+    /// period by cutting it at a concrete instant rather than at an elapsed-duration threshold.
+    DateTime(DateTime<Utc>),
+}
+
 struct StepSize {
     time: Option<(usize, PriceComponent)>,
     parking_time: Option<(usize, PriceComponent)>,
     energy: Option<(usize, PriceComponent)>,
+    reservation_time: Option<(usize, PriceComponent)>,
 }
 
 impl StepSize {
@@ -274,6 +705,7 @@ impl StepSize {
             time: None,
             parking_time: None,
             energy: None,
+            reservation_time: None,
         }
     }
 
@@ -295,24 +727,44 @@ impl StepSize {
                 self.parking_time = Some((index, parking));
             }
         }
+
+        if period.period_data.reservation_duration.is_some() {
+            if let Some(reservation) = components.reservation {
+                self.reservation_time = Some((index, reservation));
+            }
+        }
     }
 
+    /// Round `total_volume`, the summed volume across every period sharing this component, up to
+    /// the nearest multiple of `step_size` seconds, per the OCPI step-size rule. The resulting
+    /// extra volume (the difference between the rounded and unrounded total) is added to
+    /// `period_billed_volume`, the billed volume of the single period that carries this
+    /// component's price, so that it's the one reflecting the rounding in the report.
+    ///
+    /// A `step_size` of zero means no step is configured, in which case `total_volume` is
+    /// returned unchanged and `period_billed_volume` is left untouched.
+    ///
+    /// When `checked` is set (see [`Pricer::with_checked_arithmetic`]), an out-of-range rounded
+    /// volume reports [`Error::NumericOverflow`] instead of saturating to the largest
+    /// representable value.
     fn duration_step_size(
         total_volume: HoursDecimal,
         period_billed_volume: &mut HoursDecimal,
         step_size: u64,
+        checked: bool,
     ) -> Result<HoursDecimal> {
         if step_size > 0 {
             let total_seconds = total_volume.as_num_seconds_number();
             let step_size = Number::from(step_size);
+            let steps = total_seconds.checked_div(step_size)?.ceil();
 
-            let total_billed_volume = HoursDecimal::from_seconds_number(
-                total_seconds
-                    .checked_div(step_size)
-                    .unwrap_or_else(|| unreachable!("divisor is non-zero"))
-                    .ceil()
-                    .saturating_mul(step_size),
-            )?;
+            let rounded = if checked {
+                steps.checked_mul(step_size)?
+            } else {
+                steps.saturating_mul(step_size)
+            };
+
+            let total_billed_volume = HoursDecimal::from_seconds_number(rounded)?;
 
             let period_delta_volume = total_billed_volume.saturating_sub(total_volume);
             *period_billed_volume = period_billed_volume.saturating_add(period_delta_volume);
@@ -327,6 +779,7 @@ impl StepSize {
         &self,
         periods: &mut [PeriodReport],
         total: HoursDecimal,
+        checked: bool,
     ) -> Result<HoursDecimal> {
         if let (Some((time_index, price)), None) = (&self.time, &self.parking_time) {
             let period = &mut periods[*time_index];
@@ -337,7 +790,7 @@ impl StepSize {
                 .as_mut()
                 .expect("dimension should have a volume");
 
-            Self::duration_step_size(total, volume, price.step_size)
+            Self::duration_step_size(total, volume, price.step_size, checked)
         } else {
             Ok(total)
         }
@@ -347,6 +800,7 @@ impl StepSize {
         &self,
         periods: &mut [PeriodReport],
         total: HoursDecimal,
+        checked: bool,
     ) -> Result<HoursDecimal> {
         if let Some((parking_index, price)) = &self.parking_time {
             let period = &mut periods[*parking_index];
@@ -357,17 +811,53 @@ impl StepSize {
                 .as_mut()
                 .expect("dimension should have a volume");
 
-            Self::duration_step_size(total, volume, price.step_size)
+            Self::duration_step_size(total, volume, price.step_size, checked)
         } else {
             Ok(total)
         }
     }
 
-    fn apply_energy(&self, periods: &mut [PeriodReport], total_volume: Kwh) -> Kwh {
+    fn apply_reservation_time(
+        &self,
+        periods: &mut [PeriodReport],
+        total: HoursDecimal,
+        checked: bool,
+    ) -> Result<HoursDecimal> {
+        if let Some((reservation_index, price)) = &self.reservation_time {
+            let period = &mut periods[*reservation_index];
+            let volume = period
+                .dimensions
+                .reservation
+                .billed_volume
+                .as_mut()
+                .expect("dimension should have a volume");
+
+            Self::duration_step_size(total, volume, price.step_size, checked)
+        } else {
+            Ok(total)
+        }
+    }
+
+    /// When `checked` is set (see [`Pricer::with_checked_arithmetic`]), an out-of-range rounded
+    /// volume reports [`Error::NumericOverflow`] instead of saturating to the largest
+    /// representable value.
+    fn apply_energy(
+        &self,
+        periods: &mut [PeriodReport],
+        total_volume: Kwh,
+        checked: bool,
+    ) -> Result<Kwh> {
         if let Some((energy_index, price)) = &self.energy {
             if price.step_size > 0 {
                 let period = &mut periods[*energy_index];
                 let step_size = Number::from(price.step_size);
+                let steps = total_volume.watt_hours().checked_div(step_size)?.ceil();
+
+                let rounded = if checked {
+                    steps.checked_mul(step_size)?
+                } else {
+                    steps.saturating_mul(step_size)
+                };
 
                 let period_billed_volume = period
                     .dimensions
@@ -376,23 +866,16 @@ impl StepSize {
                     .as_mut()
                     .expect("dimension should have a volume");
 
-                let total_billed_volume = Kwh::from_watt_hours(
-                    total_volume
-                        .watt_hours()
-                        .checked_div(step_size)
-                        .unwrap_or_else(|| unreachable!("divisor is non-zero"))
-                        .ceil()
-                        .saturating_mul(step_size),
-                );
+                let total_billed_volume = Kwh::from_watt_hours(rounded);
 
                 let period_delta_volume = total_billed_volume.saturating_sub(total_volume);
                 *period_billed_volume = period_billed_volume.saturating_add(period_delta_volume);
 
-                return total_billed_volume;
+                return Ok(total_billed_volume);
             }
         }
 
-        total_volume
+        Ok(total_volume)
     }
 }
 
@@ -408,8 +891,16 @@ pub struct Report {
     pub tariff_id: String,
     /// Time zone that was either specified or detected.
     pub time_zone: String,
-    /// Total sum of all the costs of this transaction in the specified currency.
+    /// ISO 4217 currency code taken from the [`Cdr`]. The `total_*_cost` fields below are rounded
+    /// to this currency's minor unit, while per-period costs keep full precision.
+    pub currency: String,
+    /// Total sum of all the costs of this transaction in the specified currency, after applying
+    /// the tariff's `min_price`/`max_price` caps if any were exceeded.
     pub total_cost: Option<Price>,
+    /// The same total as [`Self::total_cost`], but before the `min_price`/`max_price` caps were
+    /// applied. Equal to `total_cost` unless a cap was hit, so callers can detect and surface the
+    /// adjustment by comparing the two.
+    pub uncapped_total_cost: Option<Price>,
     /// Total sum of all the cost related to duration of charging during this transaction, in the specified currency.
     pub total_time_cost: Option<Price>,
     /// Total duration of the charging session (including the duration of charging and not charging), in hours.
@@ -434,6 +925,280 @@ pub struct Report {
     pub total_fixed_cost: Option<Price>,
     /// Total sum of all the cost related to a reservation of a Charge Point, including fixed price components, in the specified currency.
     pub total_reservation_cost: Option<Price>,
+    /// Total duration between the reservation instant and the start of charging, in hours.
+    pub total_reservation_time: HoursDecimal,
+    /// The total reservation time after applying step-size.
+    pub billed_reservation_time: HoursDecimal,
+    /// Whether any period in this session exceeded a `max_duration` restriction, meaning the
+    /// driver overstayed for at least one tariff element. See
+    /// [`PeriodWarnings::exceeded_max_duration`] for the affected periods.
+    pub exceeded_max_duration: bool,
+    /// Which bound of the tariff's price band was applied to [`Self::total_cost`], if any. The
+    /// adjustment itself isn't distributed over any period or dimension; it's the delta between
+    /// [`Self::uncapped_total_cost`] and [`Self::total_cost`].
+    pub price_cap: Option<PriceCap>,
+}
+
+/// Which bound of a tariff's `min_price`/`max_price` band was applied to a session's total cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PriceCap {
+    /// The uncapped total fell below `min_price` and was raised to it.
+    Min,
+    /// The uncapped total rose above `max_price` and was lowered to it.
+    Max,
+}
+
+impl Report {
+    /// Project this report into a plottable, time-ordered series of the running totals charted
+    /// by a typical session graph: cumulative energy, cumulative charging time and cumulative
+    /// cost, with one point per period.
+    ///
+    /// This lives on [`Report`] rather than [`crate::session::ChargeSession`] because the
+    /// cumulative cost only exists once a tariff has been applied; [`ChargeSession`] itself only
+    /// carries the raw metered values. A period can be a split-off fragment of an original
+    /// charging period (see [`Pricer::next_split`]), so this series may have more points than the
+    /// [`Cdr`] it was priced from.
+    #[must_use]
+    pub fn cost_series(&self) -> CostSeries {
+        let mut total_energy = Kwh::zero();
+        let mut total_charging_time = HoursDecimal::zero();
+        let mut total_cost = Price::zero();
+
+        let points: Vec<CostPoint> = self
+            .periods
+            .iter()
+            .map(|period| {
+                if let Some(volume) = period.dimensions.energy.billed_volume {
+                    total_energy = total_energy.saturating_add(volume);
+                }
+
+                if let Some(volume) = period.dimensions.time.billed_volume {
+                    total_charging_time = total_charging_time.saturating_add(volume);
+                }
+
+                if let Some(cost) = period.cost() {
+                    total_cost = total_cost.saturating_add(cost);
+                }
+
+                CostPoint {
+                    date_time: period.end_date_time,
+                    total_energy,
+                    total_charging_time,
+                    total_cost,
+                }
+            })
+            .collect();
+
+        let start_date_time = self
+            .periods
+            .first()
+            .expect("a report always has at least one period")
+            .start_date_time;
+        let end_date_time = points
+            .last()
+            .map_or(start_date_time, |point| point.date_time);
+
+        CostSeries {
+            time_bounds: (start_date_time, end_date_time),
+            energy_bounds: (Kwh::zero(), total_energy),
+            cost_bounds: (Price::zero(), total_cost),
+            points,
+        }
+    }
+
+    /// Flatten this report's per-period, per-dimension breakdown into a tabular, order-preserving
+    /// list of billing lines, suitable for rendering as an invoice (e.g. as JSON or fed to a CSV
+    /// writer) without the caller having to walk [`Self::periods`]/[`Dimensions`] by hand.
+    ///
+    /// Each period contributes one [`LineItem`] per dimension that had an active price component,
+    /// followed by a trailing line per dimension summarizing that dimension's session-wide totals.
+    #[must_use]
+    pub fn line_items(&self) -> Vec<LineItem> {
+        let mut items = Vec::new();
+
+        for period in &self.periods {
+            let dimensions = &period.dimensions;
+
+            items.extend(dimensions.time.line_item(
+                LineItemKind::Time,
+                period.start_date_time,
+                period.end_date_time,
+            ));
+            items.extend(dimensions.parking_time.line_item(
+                LineItemKind::ParkingTime,
+                period.start_date_time,
+                period.end_date_time,
+            ));
+            items.extend(dimensions.flat.line_item(
+                LineItemKind::Flat,
+                period.start_date_time,
+                period.end_date_time,
+            ));
+            items.extend(dimensions.energy.line_item(
+                LineItemKind::Energy,
+                period.start_date_time,
+                period.end_date_time,
+            ));
+            items.extend(dimensions.reservation.line_item(
+                LineItemKind::Reservation,
+                period.start_date_time,
+                period.end_date_time,
+            ));
+        }
+
+        let start_date_time = self
+            .periods
+            .first()
+            .expect("a report always has at least one period")
+            .start_date_time;
+        let end_date_time = self
+            .periods
+            .last()
+            .expect("a report always has at least one period")
+            .end_date_time;
+
+        for (kind, volume, cost) in [
+            (
+                LineItemKind::Time,
+                LineItemVolume::Duration(self.billed_charging_time),
+                self.total_time_cost,
+            ),
+            (
+                LineItemKind::ParkingTime,
+                LineItemVolume::Duration(self.billed_parking_time),
+                self.total_parking_cost,
+            ),
+            (
+                LineItemKind::Flat,
+                LineItemVolume::None,
+                self.total_fixed_cost,
+            ),
+            (
+                LineItemKind::Energy,
+                LineItemVolume::Energy(self.billed_energy),
+                self.total_energy_cost,
+            ),
+            (
+                LineItemKind::Reservation,
+                LineItemVolume::Duration(self.billed_reservation_time),
+                self.total_reservation_cost,
+            ),
+        ] {
+            let Some(cost) = cost else {
+                continue;
+            };
+
+            items.push(LineItem {
+                kind,
+                summary: true,
+                start_date_time,
+                end_date_time,
+                price_component: None,
+                volume: None,
+                billed_volume: Some(volume),
+                excl_vat: cost.excl_vat,
+                incl_vat: cost.incl_vat,
+            });
+        }
+
+        items
+    }
+}
+
+/// Which billing dimension a [`LineItem`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LineItemKind {
+    /// Cost related to the duration of charging.
+    Time,
+    /// Cost related to the duration of not charging (parking).
+    ParkingTime,
+    /// A fixed cost, not depending on any measured volume.
+    Flat,
+    /// Cost related to the amount of energy transferred.
+    Energy,
+    /// Cost related to the duration between the reservation instant and the start of charging.
+    Reservation,
+}
+
+/// The billed volume of a [`LineItem`], typed according to its [`LineItemKind`].
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+pub enum LineItemVolume {
+    /// The volume for a [`LineItemKind::Energy`] line.
+    Energy(Kwh),
+    /// The volume for a [`LineItemKind::Time`], [`LineItemKind::ParkingTime`] or
+    /// [`LineItemKind::Reservation`] line.
+    Duration(HoursDecimal),
+    /// A [`LineItemKind::Flat`] line has no natural volume.
+    None,
+}
+
+impl From<Kwh> for LineItemVolume {
+    fn from(value: Kwh) -> Self {
+        Self::Energy(value)
+    }
+}
+
+impl From<HoursDecimal> for LineItemVolume {
+    fn from(value: HoursDecimal) -> Self {
+        Self::Duration(value)
+    }
+}
+
+impl From<()> for LineItemVolume {
+    fn from((): ()) -> Self {
+        Self::None
+    }
+}
+
+/// A single, flattened billing line produced by [`Report::line_items`].
+#[derive(Debug, Clone, Serialize)]
+pub struct LineItem {
+    /// Which dimension this line was billed under.
+    pub kind: LineItemKind,
+    /// Whether this is a synthetic line summarizing [`Self::kind`]'s session-wide totals, rather
+    /// than a single period's contribution. A summary line has no [`Self::price_component`], since
+    /// more than one tariff element may have contributed to it.
+    pub summary: bool,
+    /// The start of the period this line covers, or the session start for a summary line.
+    pub start_date_time: DateTime<Utc>,
+    /// The end of the period this line covers, or the session end for a summary line.
+    pub end_date_time: DateTime<Utc>,
+    /// The tariff element's price component that was active for this line, including its unit
+    /// price, VAT and step size. `None` for a summary line, which can span several elements.
+    pub price_component: Option<PriceComponent>,
+    /// The raw, metered volume before step-size was applied. `None` for a summary line, and
+    /// [`LineItemVolume::None`] for a [`LineItemKind::Flat`] line.
+    pub volume: Option<LineItemVolume>,
+    /// The volume actually billed, after step-size was applied.
+    pub billed_volume: Option<LineItemVolume>,
+    /// The cost of this line, excluding VAT.
+    pub excl_vat: Money,
+    /// The cost of this line, including VAT, if the applicable VAT rate is known.
+    pub incl_vat: Option<Money>,
+}
+
+/// A single point in a [`CostSeries`]: the cumulative energy, charging time and cost at one
+/// period's end.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CostPoint {
+    pub date_time: DateTime<Utc>,
+    pub total_energy: Kwh,
+    pub total_charging_time: HoursDecimal,
+    pub total_cost: Price,
+}
+
+/// A time-ordered, plottable series of cumulative [`CostPoint`]s across a [`Report`].
+///
+/// `time_bounds`, `energy_bounds` and `cost_bounds` give a plotting backend the `(min, max)` range
+/// for each axis so it can map values onto a pixel range without re-deriving those bounds from the
+/// points itself.
+#[derive(Debug, Clone, Serialize)]
+pub struct CostSeries {
+    pub points: Vec<CostPoint>,
+    pub time_bounds: (DateTime<Utc>, DateTime<Utc>),
+    pub energy_bounds: (Kwh, Kwh),
+    pub cost_bounds: (Price, Price),
 }
 
 /// A report for a single period that occurred during a session.
@@ -445,14 +1210,17 @@ pub struct PeriodReport {
     pub end_date_time: DateTime<Utc>,
     /// A structure that contains results per dimension.
     pub dimensions: Dimensions,
+    /// Warnings raised while resolving the active tariff components for this period.
+    pub warnings: PeriodWarnings,
 }
 
 impl PeriodReport {
-    fn new(period: &ChargePeriod, dimensions: Dimensions) -> Self {
+    fn new(period: &ChargePeriod, dimensions: Dimensions, warnings: PeriodWarnings) -> Self {
         Self {
             start_date_time: period.start_instant.date_time,
             end_date_time: period.end_instant.date_time,
             dimensions,
+            warnings,
         }
     }
 
@@ -464,6 +1232,7 @@ impl PeriodReport {
             self.dimensions.parking_time.cost(),
             self.dimensions.flat.cost(),
             self.dimensions.energy.cost(),
+            self.dimensions.reservation.cost(),
         ]
         .into_iter()
         .fold(None, |accum, next| {
@@ -480,6 +1249,32 @@ impl PeriodReport {
     }
 }
 
+/// Warnings raised while resolving a period's active tariff components. These don't stop pricing
+/// but indicate the result may not exactly reflect the tariff's intent.
+#[derive(Debug, Default, Serialize)]
+pub struct PeriodWarnings {
+    /// A tariff element was active at the start of this period but not at the end, or vice versa.
+    /// This means a restriction boundary fell strictly inside the period, so the tariff components
+    /// billed for this period may not precisely match what applied at every instant within it.
+    pub partial_tariff_element_validity: bool,
+    /// This period was split because a `max_duration` restriction on the element that was active
+    /// at its start was exceeded partway through, i.e. the driver overstayed. The portion beyond
+    /// the limit was billed separately, using whichever element's restrictions matched at that
+    /// point, if any.
+    pub exceeded_max_duration: bool,
+    /// This period's `start_instant` and `end_instant` were in different UTC offsets, i.e. the
+    /// underlying `OcpiChargingPeriod` spanned a daylight-saving transition. The period was split
+    /// at the transition so local time-of-day restrictions are still matched correctly on each
+    /// side of it; this only flags that the split happened.
+    pub dst_transition_in_period: bool,
+}
+
+impl PeriodWarnings {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+
 /// A structure containing a report for each dimension.
 #[derive(Serialize)]
 pub struct Dimensions {
@@ -491,6 +1286,8 @@ pub struct Dimensions {
     pub time: DimensionReport<HoursDecimal>,
     /// The parking time dimension.
     pub parking_time: DimensionReport<HoursDecimal>,
+    /// The reservation dimension.
+    pub reservation: DimensionReport<HoursDecimal>,
 }
 
 impl Dimensions {
@@ -503,6 +1300,10 @@ impl Dimensions {
             time: DimensionReport::new(components.time, data.charging_duration.map(Into::into)),
             energy: DimensionReport::new(components.energy, data.energy),
             flat: DimensionReport::new(components.flat, Some(())),
+            reservation: DimensionReport::new(
+                components.reservation,
+                data.reservation_duration.map(Into::into),
+            ),
         }
     }
 }
@@ -560,6 +1361,32 @@ impl<V: Dimension> DimensionReport<V> {
     }
 }
 
+impl<V: Dimension + Into<LineItemVolume>> DimensionReport<V> {
+    /// The [`LineItem`] billed for this dimension during a single period, or `None` if no price
+    /// component was active for it.
+    fn line_item(
+        &self,
+        kind: LineItemKind,
+        start_date_time: DateTime<Utc>,
+        end_date_time: DateTime<Utc>,
+    ) -> Option<LineItem> {
+        let price_component = self.price?;
+        let cost = self.cost()?;
+
+        Some(LineItem {
+            kind,
+            summary: false,
+            start_date_time,
+            end_date_time,
+            price_component: Some(price_component),
+            volume: self.volume.map(Into::into),
+            billed_volume: self.billed_volume.map(Into::into),
+            excl_vat: cost.excl_vat,
+            incl_vat: cost.incl_vat,
+        })
+    }
+}
+
 /// An OCPI tariff dimension
 pub trait Dimension: Copy {
     /// The cost of this dimension at a certain price.
@@ -583,3 +1410,62 @@ impl Dimension for HoursDecimal {
         price.time_cost(*self)
     }
 }
+
+#[cfg(test)]
+mod step_size_tests {
+    use chrono::Duration;
+
+    use super::StepSize;
+    use crate::types::time::HoursDecimal;
+
+    #[test]
+    fn zero_step_size_leaves_volume_unchanged() {
+        let total: HoursDecimal = Duration::try_seconds(250).unwrap().into();
+        let mut billed = total;
+
+        let result = StepSize::duration_step_size(total, &mut billed, 0, false).unwrap();
+
+        assert_eq!(result, total);
+        assert_eq!(billed, total);
+    }
+
+    #[test]
+    fn zero_volume_stays_zero() {
+        let total = HoursDecimal::zero();
+        let mut billed = total;
+
+        let result = StepSize::duration_step_size(total, &mut billed, 900, false).unwrap();
+
+        assert_eq!(result, HoursDecimal::zero());
+        assert_eq!(billed, HoursDecimal::zero());
+    }
+
+    #[test]
+    fn single_period_rounds_up_to_the_next_step() {
+        // 250 seconds billed in steps of 300 seconds rounds up to a single step.
+        let total: HoursDecimal = Duration::try_seconds(250).unwrap().into();
+        let mut billed = total;
+
+        let result = StepSize::duration_step_size(total, &mut billed, 300, false).unwrap();
+
+        assert_eq!(result, Duration::try_seconds(300).unwrap().into());
+        assert_eq!(billed, Duration::try_seconds(300).unwrap().into());
+    }
+
+    #[test]
+    fn accumulation_across_periods_rounds_the_session_total_once() {
+        // Two periods of 200 seconds each share the same step-size; the step should be applied
+        // to their combined 400 second total rather than to each period separately, otherwise
+        // 200 seconds would be rounded up to 300 twice instead of once to 600.
+        let period_volume: HoursDecimal = Duration::try_seconds(200).unwrap().into();
+        let total = period_volume.saturating_add(period_volume);
+        let mut billed = period_volume;
+
+        let result = StepSize::duration_step_size(total, &mut billed, 300, false).unwrap();
+
+        assert_eq!(result, Duration::try_seconds(600).unwrap().into());
+        // The 200 second delta between the rounded and unrounded total is billed onto the
+        // period that holds the reference, on top of its own already-billed volume.
+        assert_eq!(billed, Duration::try_seconds(400).unwrap().into());
+    }
+}
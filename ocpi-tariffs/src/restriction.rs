@@ -1,8 +1,8 @@
-use std::collections::HashSet;
+use alloc::collections::VecDeque;
 
-use chrono::{Duration, NaiveDate, NaiveTime, Timelike, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike, Weekday};
 
-use crate::ocpi::tariff::OcpiTariffRestriction;
+use crate::ocpi::tariff::{OcpiTariffRestriction, ReservationRestrictionType};
 use crate::session::{InstantData, PeriodData};
 use crate::types::{Ampere, Kw, Kwh};
 
@@ -10,6 +10,9 @@ pub fn collect_restrictions(restriction: &OcpiTariffRestriction) -> Vec<Restrict
     let mut collected = Vec::new();
 
     match (restriction.start_time, restriction.end_time) {
+        // `end_time` earlier than `start_time` means the window crosses midnight, e.g. a night
+        // tariff running 22:00-06:00, so it needs the wrap-around variant instead of a plain
+        // start/end pair.
         (Some(start_time), Some(end_time))
             if NaiveTime::from(end_time) < NaiveTime::from(start_time) =>
         {
@@ -70,18 +73,142 @@ pub fn collect_restrictions(restriction: &OcpiTariffRestriction) -> Vec<Restrict
     }
 
     if !restriction.day_of_week.is_empty() {
-        collected.push(Restriction::DayOfWeek(HashSet::from_iter(
+        collected.push(Restriction::DayOfWeek(WeekdaySet::from_iter(
             restriction.day_of_week.iter().copied().map(Into::into),
         )))
     }
 
+    if restriction.reservation == Some(ReservationRestrictionType::Reservation) {
+        collected.push(Restriction::Reservation)
+    }
+
+    // `rrule` is anchored to this same restriction's `start_date`; without one there is no
+    // `DTSTART` to expand from, so the rule is ignored rather than guessing an anchor.
+    if let (Some(rrule), Some(start_date)) = (&restriction.rrule, restriction.start_date) {
+        if let Some(recurrence) = parse_rrule(rrule, start_date.into()) {
+            collected.push(Restriction::Recurring(recurrence))
+        }
+    }
+
     collected
 }
 
+/// Parse a (subset of) RFC 5545 `RRULE` value, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TU`, into a
+/// [`Recurrence`] anchored at `dtstart`.
+///
+/// Understands `FREQ`, `INTERVAL`, `BYDAY` (including ordinal weekdays like `1MO`/`-1SU`),
+/// `BYMONTHDAY`, `BYMONTH`, `BYSETPOS`, `COUNT`, and `UNTIL` (the RFC 5545 `DATE` form,
+/// `YYYYMMDD`; the `DATE-TIME` form isn't supported since occurrence matching here is date-only).
+/// Unrecognized parts are skipped so a rule using fields this crate doesn't model still parses
+/// for the parts it does. Returns `None` if `FREQ` is missing or unrecognized.
+fn parse_rrule(rrule: &str, dtstart: NaiveDate) -> Option<Recurrence> {
+    let mut frequency = None;
+    let mut interval = 1;
+    let mut count = None;
+    let mut until = None;
+    let mut by_day = Vec::new();
+    let mut by_month_day = Vec::new();
+    let mut by_month = Vec::new();
+    let mut by_set_pos = Vec::new();
+
+    for part in rrule.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+
+        match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = match value.trim().to_ascii_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => None,
+                }
+            }
+            "INTERVAL" => interval = value.trim().parse().unwrap_or(1),
+            "COUNT" => count = value.trim().parse().ok(),
+            "UNTIL" => until = NaiveDate::parse_from_str(value.trim(), "%Y%m%d").ok(),
+            "BYDAY" => {
+                by_day.extend(value.split(',').filter_map(|token| parse_by_day(token.trim())))
+            }
+            "BYMONTHDAY" => {
+                by_month_day.extend(value.split(',').filter_map(|token| token.trim().parse().ok()))
+            }
+            "BYMONTH" => {
+                by_month.extend(value.split(',').filter_map(|token| token.trim().parse().ok()))
+            }
+            "BYSETPOS" => {
+                by_set_pos.extend(value.split(',').filter_map(|token| token.trim().parse().ok()))
+            }
+            _ => {}
+        }
+    }
+
+    let mut recurrence = Recurrence::new(frequency?, interval, dtstart);
+
+    if let Some(count) = count {
+        recurrence = recurrence.count(count);
+    }
+
+    if let Some(until) = until {
+        recurrence = recurrence.until(until);
+    }
+
+    for day in by_day {
+        recurrence = recurrence.by_day(day);
+    }
+
+    for day in by_month_day {
+        recurrence = recurrence.by_month_day(day);
+    }
+
+    for month in by_month {
+        recurrence = recurrence.by_month(month);
+    }
+
+    for pos in by_set_pos {
+        recurrence = recurrence.by_set_pos(pos);
+    }
+
+    Some(recurrence)
+}
+
+/// Parse an RFC 5545 `BYDAY` token, e.g. `MO`, `1MO` (first Monday), or `-1SU` (last Sunday).
+fn parse_by_day(token: &str) -> Option<ByDay> {
+    if !token.is_ascii() {
+        return None;
+    }
+
+    let split_at = token.len().checked_sub(2)?;
+    let (ordinal, weekday) = token.split_at(split_at);
+
+    let weekday = match weekday {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+
+    if ordinal.is_empty() {
+        Some(ByDay::new(weekday))
+    } else {
+        ordinal.parse().ok().map(|ordinal| ByDay::nth(weekday, ordinal))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Restriction {
     StartTime(NaiveTime),
     EndTime(NaiveTime),
+    /// A time-of-day window that wraps past midnight, e.g. `start_time` "22:00" and `end_time`
+    /// "06:00" for an overnight tariff. Matches `[start_time, 24:00) ∪ [00:00, end_time)`, unlike
+    /// [`Self::StartTime`]/[`Self::EndTime`] which are only ever combined when the window doesn't
+    /// wrap.
     WrappingTime {
         start_time: NaiveTime,
         end_time: NaiveTime,
@@ -96,8 +223,497 @@ pub enum Restriction {
     MaxPower(Kw),
     MinDuration(Duration),
     MaxDuration(Duration),
-    DayOfWeek(HashSet<Weekday>),
+    DayOfWeek(WeekdaySet),
     Reservation,
+    /// Matches when the instant's local date is a holiday according to the [`HolidayCalendar`]
+    /// supplied to the [`crate::pricer::Pricer`].
+    ///
+    /// OCPI itself has no holiday restriction field, so this variant is meant for restriction
+    /// lists that are composed programmatically rather than parsed from an [`OcpiTariffRestriction`].
+    Holiday,
+    /// The inverse of [`Self::Holiday`].
+    NotHoliday,
+    /// Matches when the instant's local date is an occurrence of `rule`.
+    ///
+    /// OCPI has no recurring-date restriction field; [`collect_restrictions`] builds this variant
+    /// from a restriction's non-standard `rrule` field, and it can also be composed
+    /// programmatically.
+    Recurring(Recurrence),
+}
+
+/// A bitmask of the seven weekdays, one bit per [`Weekday::num_days_from_monday`].
+///
+/// `chrono::Weekday` doesn't implement `Ord`, so it can't go in a `BTreeSet`, and `core`/`alloc`
+/// have no hash-based set without pulling in an extra dependency for a hasher - a bitmask needs
+/// neither and keeps this type usable in a `no_std` build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WeekdaySet(u8);
+
+impl WeekdaySet {
+    pub(crate) fn contains(&self, day: &Weekday) -> bool {
+        self.0 & (1 << day.num_days_from_monday()) != 0
+    }
+}
+
+impl FromIterator<Weekday> for WeekdaySet {
+    fn from_iter<T: IntoIterator<Item = Weekday>>(iter: T) -> Self {
+        let mut mask = 0u8;
+
+        for day in iter {
+            mask |= 1 << day.num_days_from_monday();
+        }
+
+        Self(mask)
+    }
+}
+
+/// Frequency at which a [`Recurrence`] repeats, modeled after the subset of RFC 5545 `FREQ`
+/// values tariffs commonly need for recurring special-day pricing (e.g. "first of every month").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A weekday occurring within a [`Recurrence`], modeled after RFC 5545's `BYDAY`, e.g. `1MO`
+/// (first Monday) or `-1FR` (last Friday) of the period.
+///
+/// `ordinal` is only meaningful for a [`Frequency::Monthly`] or [`Frequency::Yearly`] recurrence;
+/// it is ignored for `Daily`/`Weekly`, where every occurrence of `weekday` in the period matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub weekday: Weekday,
+    pub ordinal: Option<i32>,
+}
+
+impl ByDay {
+    #[must_use]
+    pub fn new(weekday: Weekday) -> Self {
+        Self {
+            weekday,
+            ordinal: None,
+        }
+    }
+
+    /// The `ordinal`'th occurrence of `weekday` in the period, e.g. `1` for "the first Monday",
+    /// `-1` for "the last Friday".
+    #[must_use]
+    pub fn nth(weekday: Weekday, ordinal: i32) -> Self {
+        Self {
+            weekday,
+            ordinal: Some(ordinal),
+        }
+    }
+}
+
+/// A small recurrence rule: an occurrence on `start_date` that repeats every `interval` units of
+/// `frequency`, optionally narrowed by `by_day`/`by_month_day`/`by_month` and bounded by `until`
+/// and/or `count`.
+///
+/// This is not a general RFC 5545 implementation, just enough to express recurring tariff
+/// schedules like "every year on Dec 25", "every second Monday" or "the last weekday of every
+/// month".
+#[derive(Debug, Clone)]
+pub struct Recurrence {
+    frequency: Frequency,
+    interval: u32,
+    start_date: NaiveDate,
+    until: Option<NaiveDate>,
+    count: Option<u32>,
+    by_day: Vec<ByDay>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    by_set_pos: Vec<i32>,
+    window_days: Option<u32>,
+}
+
+impl Recurrence {
+    /// Create a new recurrence starting on `start_date`, repeating every `interval` units of
+    /// `frequency`. An `interval` of zero is treated as one.
+    #[must_use]
+    pub fn new(frequency: Frequency, interval: u32, start_date: NaiveDate) -> Self {
+        Self {
+            frequency,
+            interval: interval.max(1),
+            start_date,
+            until: None,
+            count: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            window_days: None,
+        }
+    }
+
+    /// Bound the recurrence to produce no occurrence after `until`, typically the end of the
+    /// charge session being priced, so that an otherwise-unbounded rule can't be expanded
+    /// forever.
+    #[must_use]
+    pub fn until(mut self, until: NaiveDate) -> Self {
+        self.until = Some(until);
+
+        self
+    }
+
+    /// Bound the recurrence to at most `count` occurrences, counted from `start_date`.
+    #[must_use]
+    pub fn count(mut self, count: u32) -> Self {
+        self.count = Some(count);
+
+        self
+    }
+
+    /// Restrict each period (week/month/year, depending on [`Frequency`]) to the occurrences of
+    /// `day` within it, akin to RFC 5545's `BYDAY`. May be called multiple times.
+    #[must_use]
+    pub fn by_day(mut self, day: ByDay) -> Self {
+        self.by_day.push(day);
+
+        self
+    }
+
+    /// Restrict each month (for `Monthly`/`Yearly`) to `day`, akin to RFC 5545's `BYMONTHDAY`. A
+    /// negative value counts from the end of the month, e.g. `-1` is the last day. May be called
+    /// multiple times.
+    #[must_use]
+    pub fn by_month_day(mut self, day: i32) -> Self {
+        self.by_month_day.push(day);
+
+        self
+    }
+
+    /// Restrict a `Yearly` recurrence to `month`, akin to RFC 5545's `BYMONTH`. May be called
+    /// multiple times.
+    #[must_use]
+    pub fn by_month(mut self, month: u32) -> Self {
+        self.by_month.push(month);
+
+        self
+    }
+
+    /// Rank each period's candidates (after `by_day`/`by_month_day`/`by_month` narrow a period
+    /// down to its full set of matches) and keep only the `pos`'th one, akin to RFC 5545's
+    /// `BYSETPOS`. A positive `pos` counts from the start of the period's sorted candidates, a
+    /// negative one counts back from the end (`-1` is the last candidate). May be called
+    /// multiple times.
+    #[must_use]
+    pub fn by_set_pos(mut self, pos: i32) -> Self {
+        self.by_set_pos.push(pos);
+
+        self
+    }
+
+    /// Extend each occurrence into a `days`-long window, so that [`Self::matches`] accepts any
+    /// date from an occurrence's start up to (but not including) `days` later, rather than only
+    /// the occurrence's own date. Lets a single recurring rule express a season, e.g. a yearly
+    /// occurrence on Dec 1 with a 90-day window for "winter pricing". Without this, every
+    /// occurrence only ever matches its own single day.
+    #[must_use]
+    pub fn window(mut self, days: u32) -> Self {
+        self.window_days = Some(days);
+
+        self
+    }
+
+    /// All concrete occurrences of this rule up to and including `window_end` (further capped by
+    /// [`Self::until`]/[`Self::count`] if set), so expansion always terminates even for an
+    /// unbounded rule.
+    pub(crate) fn expand(&self, window_end: NaiveDate) -> Vec<NaiveDate> {
+        let end = self.until.map_or(window_end, |until| until.min(window_end));
+
+        let mut dates = Vec::new();
+
+        for date in self.occurrences() {
+            if date > end {
+                break;
+            }
+
+            dates.push(date);
+
+            if self.count.is_some_and(|count| dates.len() as u32 >= count) {
+                break;
+            }
+        }
+
+        dates
+    }
+
+    /// Whether `date` falls within an occurrence of this rule: on or after some occurrence's own
+    /// date, and before that occurrence's [`Self::window`] ends (a plain, windowless recurrence
+    /// behaves as a 1-day window, so this reduces to exact-date matching). Finds the nearest
+    /// occurrence at or before `date` by walking occurrences in chronological order and stopping
+    /// as soon as one lands after `date`, rather than materialising every occurrence up to it.
+    pub(crate) fn matches(&self, date: NaiveDate) -> bool {
+        if date < self.start_date {
+            return false;
+        }
+
+        let mut seen = 0;
+        let mut nearest = None;
+
+        for occurrence in self.occurrences() {
+            if self.until.is_some_and(|until| occurrence > until) {
+                break;
+            }
+
+            if self.count.is_some_and(|count| seen >= count) {
+                break;
+            }
+
+            if occurrence > date {
+                break;
+            }
+
+            nearest = Some(occurrence);
+            seen += 1;
+        }
+
+        let window = Duration::days(i64::from(self.window_days.unwrap_or(1).max(1)));
+
+        nearest.is_some_and(|occurrence| date < occurrence + window)
+    }
+
+    /// All occurrences of this rule from `start_date` onwards, in chronological order. This is
+    /// unbounded; callers must stop draining once past whatever limit applies ([`Self::until`],
+    /// a session window, or [`Self::count`]).
+    fn occurrences(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        let mut step = 0;
+        let mut queue: VecDeque<NaiveDate> = VecDeque::new();
+
+        core::iter::from_fn(move || loop {
+            if let Some(date) = queue.pop_front() {
+                return Some(date);
+            }
+
+            let anchor = self.period_anchor(step)?;
+            step += 1;
+
+            for date in self.period_candidates(anchor) {
+                if date >= self.start_date {
+                    queue.push_back(date);
+                }
+            }
+        })
+    }
+
+    /// The anchor date of the `step`'th period after [`Self::start_date`] (the zeroth period is
+    /// the one containing `start_date`). For `Monthly`/`Yearly` frequencies where the naive
+    /// target day doesn't exist in the resulting month (e.g. Jan 31 + 1 month, or Feb 29 in a
+    /// non-leap year), the day-of-month is decremented until a valid date is found.
+    fn period_anchor(&self, step: u32) -> Option<NaiveDate> {
+        let amount = i64::from(step) * i64::from(self.interval);
+
+        match self.frequency {
+            Frequency::Daily => self.start_date.checked_add_signed(Duration::days(amount)),
+            Frequency::Weekly => self.start_date.checked_add_signed(Duration::weeks(amount)),
+            Frequency::Monthly => Self::add_months(self.start_date, amount),
+            Frequency::Yearly => Self::add_months(self.start_date, amount.checked_mul(12)?),
+        }
+    }
+
+    /// The occurrences a period (anchored on `anchor`) contributes, after applying the `by_day`
+    /// /`by_month_day`/`by_month` filters and then `by_set_pos`. Without any `BY*` filter, a
+    /// period contributes exactly `anchor` itself.
+    fn period_candidates(&self, anchor: NaiveDate) -> Vec<NaiveDate> {
+        let candidates = match self.frequency {
+            Frequency::Daily => {
+                if self.by_day.is_empty()
+                    || self.by_day.iter().any(|d| d.weekday == anchor.weekday())
+                {
+                    vec![anchor]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => self.week_candidates(anchor),
+            Frequency::Monthly => self.month_candidates(anchor.year(), anchor.month(), anchor),
+            Frequency::Yearly => self.year_candidates(anchor.year(), anchor),
+        };
+
+        self.apply_set_pos(candidates)
+    }
+
+    /// Restrict `candidates`, a period's full sorted set of `BYDAY`/`BYMONTHDAY`/`BYMONTH`
+    /// matches, to the positions in `by_set_pos`. See [`Self::by_set_pos`].
+    fn apply_set_pos(&self, mut candidates: Vec<NaiveDate>) -> Vec<NaiveDate> {
+        if self.by_set_pos.is_empty() {
+            return candidates;
+        }
+
+        candidates.sort();
+
+        let len = candidates.len() as i32;
+
+        let mut selected: Vec<NaiveDate> = self
+            .by_set_pos
+            .iter()
+            .filter_map(|&pos| {
+                let index = if pos > 0 { pos - 1 } else { len + pos };
+
+                (index >= 0 && index < len).then(|| candidates[index as usize])
+            })
+            .collect();
+
+        selected.sort();
+        selected.dedup();
+        selected
+    }
+
+    fn week_candidates(&self, anchor: NaiveDate) -> Vec<NaiveDate> {
+        if self.by_day.is_empty() {
+            return vec![anchor];
+        }
+
+        let mut dates: Vec<NaiveDate> = (0..7)
+            .filter_map(|offset| anchor.checked_add_signed(Duration::days(offset)))
+            .filter(|date| self.by_day.iter().any(|d| d.weekday == date.weekday()))
+            .collect();
+
+        dates.sort();
+        dates
+    }
+
+    fn month_candidates(&self, year: i32, month: u32, fallback: NaiveDate) -> Vec<NaiveDate> {
+        if !self.by_month.is_empty() && !self.by_month.contains(&month) {
+            return Vec::new();
+        }
+
+        if self.by_month_day.is_empty() && self.by_day.is_empty() {
+            return vec![fallback];
+        }
+
+        let mut dates = Vec::new();
+
+        for &day in &self.by_month_day {
+            if let Some(date) = Self::nth_day_of_month(year, month, day) {
+                dates.push(date);
+            }
+        }
+
+        for by_day in &self.by_day {
+            dates.extend(Self::nth_weekday_of_month(
+                year,
+                month,
+                by_day.weekday,
+                by_day.ordinal,
+            ));
+        }
+
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+
+    fn year_candidates(&self, year: i32, fallback: NaiveDate) -> Vec<NaiveDate> {
+        if self.by_month.is_empty() {
+            return self.month_candidates(year, fallback.month(), fallback);
+        }
+
+        let mut months = self.by_month.clone();
+        months.sort_unstable();
+        months.dedup();
+
+        let mut dates = Vec::new();
+
+        for month in months {
+            if self.by_month_day.is_empty() && self.by_day.is_empty() {
+                if let Some(date) = NaiveDate::from_ymd_opt(year, month, self.start_date.day()) {
+                    dates.push(date);
+                }
+            } else {
+                dates.extend(self.month_candidates(year, month, fallback));
+            }
+        }
+
+        dates.sort();
+        dates
+    }
+
+    /// The `nth` day of `year`-`month`, RFC 5545 `BYMONTHDAY` style: positive counts from the
+    /// start of the month, negative counts back from its end (`-1` is the last day).
+    fn nth_day_of_month(year: i32, month: u32, nth: i32) -> Option<NaiveDate> {
+        match nth.cmp(&0) {
+            core::cmp::Ordering::Greater => {
+                NaiveDate::from_ymd_opt(year, month, u32::try_from(nth).ok()?)
+            }
+            core::cmp::Ordering::Less => {
+                let last_day = Self::last_day_of_month(year, month)?;
+                let day = u32::try_from(i64::from(last_day.day()) + i64::from(nth) + 1).ok()?;
+
+                NaiveDate::from_ymd_opt(year, month, day)
+            }
+            core::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// The `ordinal`'th occurrence of `weekday` in `year`-`month`, RFC 5545 `BYDAY` style, or
+    /// every occurrence if `ordinal` is `None`.
+    fn nth_weekday_of_month(
+        year: i32,
+        month: u32,
+        weekday: Weekday,
+        ordinal: Option<i32>,
+    ) -> Vec<NaiveDate> {
+        let Some(last_day) = Self::last_day_of_month(year, month) else {
+            return Vec::new();
+        };
+
+        let matching: Vec<NaiveDate> = (1..=last_day.day())
+            .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+            .filter(|date| date.weekday() == weekday)
+            .collect();
+
+        match ordinal {
+            None => matching,
+            Some(n) if n > 0 => matching
+                .get(n as usize - 1)
+                .copied()
+                .into_iter()
+                .collect(),
+            Some(n) if n < 0 => {
+                let index = matching.len() as i32 + n;
+
+                if index >= 0 {
+                    matching.get(index as usize).copied().into_iter().collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+        let next_month_first = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)
+        }?;
+
+        next_month_first.pred_opt()
+    }
+
+    /// Advance `date` by `months`. When the naive target day doesn't exist in the resulting
+    /// month (e.g. Jan 31 + 1 month, or Feb 29 in a non-leap year), the day-of-month is
+    /// decremented until a valid date is found.
+    fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+        let total_months = i64::from(date.year()) * 12 + i64::from(date.month0()) + months;
+        let year = i32::try_from(total_months.div_euclid(12)).ok()?;
+        let month = u32::try_from(total_months.rem_euclid(12)).ok()? + 1;
+
+        let mut day = date.day();
+
+        loop {
+            if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+                return Some(date);
+            }
+
+            day = day.checked_sub(1)?;
+        }
+    }
 }
 
 impl Restriction {
@@ -118,6 +734,9 @@ impl Restriction {
             &Self::MinDuration(min_duration) => instant.total_duration >= min_duration,
             &Self::MaxDuration(max_duration) => instant.total_duration < max_duration,
             Self::DayOfWeek(days) => days.contains(&instant.local_weekday()),
+            Self::Holiday => instant.is_holiday(),
+            Self::NotHoliday => !instant.is_holiday(),
+            Self::Recurring(rule) => rule.matches(instant.local_date()),
             _ => true,
         }
     }
@@ -155,6 +774,21 @@ impl Restriction {
                 let is_at_midnight = instant.local_time().num_seconds_from_midnight() == 0;
                 includes_weekday || (includes_day_before && is_at_midnight)
             }
+            Self::Holiday => {
+                let is_at_midnight = instant.local_time().num_seconds_from_midnight() == 0;
+                instant.is_holiday() || (instant.is_day_before_holiday() && is_at_midnight)
+            }
+            Self::NotHoliday => !instant.is_holiday(),
+            Self::Recurring(rule) => {
+                let current = instant.local_date();
+                let is_at_midnight = instant.local_time().num_seconds_from_midnight() == 0;
+                let includes_day_before = is_at_midnight
+                    && current
+                        .pred_opt()
+                        .is_some_and(|previous| rule.matches(previous));
+
+                rule.matches(current) || includes_day_before
+            }
             _ => true,
         }
     }
@@ -178,8 +812,276 @@ impl Restriction {
                 .max_power
                 .map(|power| power < max_power)
                 .unwrap_or(true),
-            &Self::Reservation => todo!(),
+            // A period is a reservation period if it has a reservation volume but no energy was
+            // actually delivered during it.
+            Self::Reservation => state.reservation_duration.is_some() && state.energy.is_none(),
             _ => true,
         }
     }
 }
+
+#[cfg(test)]
+mod recurrence_tests {
+    use chrono::{NaiveDate, Weekday};
+
+    use super::{ByDay, Frequency, Recurrence};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn yearly_recurrence_matches_every_year() {
+        let rule = Recurrence::new(Frequency::Yearly, 1, date(2020, 12, 25));
+
+        assert!(rule.matches(date(2020, 12, 25)));
+        assert!(rule.matches(date(2023, 12, 25)));
+        assert!(!rule.matches(date(2023, 12, 24)));
+        assert!(!rule.matches(date(2019, 12, 25)));
+    }
+
+    #[test]
+    fn monthly_recurrence_decrements_into_shorter_months() {
+        // Jan 31 + 1 month has no Feb 31, so it should fall back to the last day of February.
+        let rule = Recurrence::new(Frequency::Monthly, 1, date(2023, 1, 31));
+
+        assert!(rule.matches(date(2023, 1, 31)));
+        assert!(rule.matches(date(2023, 2, 28)));
+        assert!(rule.matches(date(2023, 3, 31)));
+        assert!(rule.matches(date(2023, 4, 30)));
+        assert!(!rule.matches(date(2023, 2, 27)));
+    }
+
+    #[test]
+    fn yearly_recurrence_on_leap_day_falls_back_in_non_leap_years() {
+        let rule = Recurrence::new(Frequency::Yearly, 1, date(2020, 2, 29));
+
+        assert!(rule.matches(date(2020, 2, 29)));
+        assert!(rule.matches(date(2021, 2, 28)));
+        assert!(rule.matches(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn interval_skips_occurrences() {
+        // Every second week starting on a Monday.
+        let rule = Recurrence::new(Frequency::Weekly, 2, date(2023, 1, 2));
+
+        assert!(rule.matches(date(2023, 1, 2)));
+        assert!(!rule.matches(date(2023, 1, 9)));
+        assert!(rule.matches(date(2023, 1, 16)));
+    }
+
+    #[test]
+    fn expand_is_capped_by_the_session_window() {
+        let rule = Recurrence::new(Frequency::Daily, 1, date(2023, 1, 1));
+
+        let occurrences = rule.expand(date(2023, 1, 5));
+
+        assert_eq!(
+            occurrences,
+            vec![
+                date(2023, 1, 1),
+                date(2023, 1, 2),
+                date(2023, 1, 3),
+                date(2023, 1, 4),
+                date(2023, 1, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_is_further_capped_by_until() {
+        let rule = Recurrence::new(Frequency::Daily, 1, date(2023, 1, 1)).until(date(2023, 1, 3));
+
+        let occurrences = rule.expand(date(2023, 12, 31));
+
+        assert_eq!(
+            occurrences,
+            vec![date(2023, 1, 1), date(2023, 1, 2), date(2023, 1, 3)]
+        );
+    }
+
+    #[test]
+    fn date_before_start_never_matches() {
+        let rule = Recurrence::new(Frequency::Daily, 1, date(2023, 6, 1));
+
+        assert!(!rule.matches(date(2023, 5, 31)));
+    }
+
+    #[test]
+    fn by_day_restricts_weekly_recurrence_to_matching_weekdays() {
+        // Every Monday and Thursday, starting on a Monday.
+        let rule = Recurrence::new(Frequency::Weekly, 1, date(2023, 1, 2))
+            .by_day(ByDay::new(Weekday::Mon))
+            .by_day(ByDay::new(Weekday::Thu));
+
+        let occurrences = rule.expand(date(2023, 1, 15));
+
+        assert_eq!(
+            occurrences,
+            vec![
+                date(2023, 1, 2),
+                date(2023, 1, 5),
+                date(2023, 1, 9),
+                date(2023, 1, 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn by_day_with_ordinal_picks_the_nth_weekday_of_the_month() {
+        // The last Friday of every month.
+        let rule = Recurrence::new(Frequency::Monthly, 1, date(2023, 1, 1))
+            .by_day(ByDay::nth(Weekday::Fri, -1));
+
+        assert!(rule.matches(date(2023, 1, 27)));
+        assert!(rule.matches(date(2023, 2, 24)));
+        assert!(!rule.matches(date(2023, 1, 20)));
+    }
+
+    #[test]
+    fn by_month_day_supports_negative_offsets_from_month_end() {
+        // The second-to-last day of every month.
+        let rule = Recurrence::new(Frequency::Monthly, 1, date(2023, 1, 1)).by_month_day(-2);
+
+        assert!(rule.matches(date(2023, 1, 30)));
+        assert!(rule.matches(date(2023, 2, 27)));
+        assert!(!rule.matches(date(2023, 1, 31)));
+    }
+
+    #[test]
+    fn by_month_restricts_yearly_recurrence_to_selected_months() {
+        // The first of June and December every year.
+        let rule = Recurrence::new(Frequency::Yearly, 1, date(2023, 6, 1))
+            .by_month(6)
+            .by_month(12)
+            .by_month_day(1);
+
+        let occurrences = rule.expand(date(2023, 12, 31));
+
+        assert_eq!(occurrences, vec![date(2023, 6, 1), date(2023, 12, 1)]);
+    }
+
+    #[test]
+    fn count_stops_expansion_after_the_given_number_of_occurrences() {
+        let rule = Recurrence::new(Frequency::Daily, 1, date(2023, 1, 1)).count(3);
+
+        let occurrences = rule.expand(date(2023, 12, 31));
+
+        assert_eq!(
+            occurrences,
+            vec![date(2023, 1, 1), date(2023, 1, 2), date(2023, 1, 3)]
+        );
+
+        assert!(rule.matches(date(2023, 1, 3)));
+        assert!(!rule.matches(date(2023, 1, 4)));
+    }
+
+    #[test]
+    fn by_set_pos_picks_the_nth_candidate_of_the_period() {
+        // The last weekday (Mon-Fri) of every month.
+        let rule = Recurrence::new(Frequency::Monthly, 1, date(2023, 1, 1))
+            .by_day(ByDay::new(Weekday::Mon))
+            .by_day(ByDay::new(Weekday::Tue))
+            .by_day(ByDay::new(Weekday::Wed))
+            .by_day(ByDay::new(Weekday::Thu))
+            .by_day(ByDay::new(Weekday::Fri))
+            .by_set_pos(-1);
+
+        assert!(rule.matches(date(2023, 1, 31)));
+        assert!(rule.matches(date(2023, 2, 28)));
+        assert!(!rule.matches(date(2023, 1, 30)));
+    }
+
+    #[test]
+    fn window_extends_a_yearly_occurrence_into_a_season() {
+        // Winter pricing: a 90-day window starting every Dec 1.
+        let rule = Recurrence::new(Frequency::Yearly, 1, date(2023, 12, 1)).window(90);
+
+        assert!(rule.matches(date(2023, 12, 1)));
+        assert!(rule.matches(date(2024, 1, 15)));
+        assert!(!rule.matches(date(2024, 3, 2)));
+        // The following year's window hasn't started yet.
+        assert!(!rule.matches(date(2024, 11, 30)));
+        assert!(rule.matches(date(2024, 12, 1)));
+    }
+
+    #[test]
+    fn without_a_window_only_the_occurrence_date_matches() {
+        let rule = Recurrence::new(Frequency::Yearly, 1, date(2023, 12, 1));
+
+        assert!(rule.matches(date(2023, 12, 1)));
+        assert!(!rule.matches(date(2023, 12, 2)));
+    }
+}
+
+#[cfg(test)]
+mod parse_rrule_tests {
+    use chrono::{NaiveDate, Weekday};
+
+    use super::parse_rrule;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn biweekly_by_day_matches_only_the_named_weekdays() {
+        let rule = parse_rrule("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TU", date(2023, 1, 2)).unwrap();
+
+        assert!(rule.matches(date(2023, 1, 2)));
+        assert!(rule.matches(date(2023, 1, 3)));
+        assert!(!rule.matches(date(2023, 1, 4)));
+        // The week in between the interval is skipped entirely.
+        assert!(!rule.matches(date(2023, 1, 9)));
+        assert!(rule.matches(date(2023, 1, 16)));
+    }
+
+    #[test]
+    fn first_monday_of_the_month_uses_ordinal_by_day() {
+        let rule = parse_rrule("FREQ=MONTHLY;BYDAY=1MO", date(2023, 1, 2)).unwrap();
+
+        assert!(rule.matches(date(2023, 1, 2)));
+        assert!(!rule.matches(date(2023, 1, 9)));
+        assert!(rule.matches(date(2023, 2, 6)));
+    }
+
+    #[test]
+    fn count_and_until_bound_the_expansion() {
+        let bounded_by_count = parse_rrule("FREQ=DAILY;COUNT=2", date(2023, 1, 1)).unwrap();
+
+        assert!(bounded_by_count.matches(date(2023, 1, 2)));
+        assert!(!bounded_by_count.matches(date(2023, 1, 3)));
+
+        let bounded_by_until =
+            parse_rrule("FREQ=DAILY;UNTIL=20230102", date(2023, 1, 1)).unwrap();
+
+        assert!(bounded_by_until.matches(date(2023, 1, 2)));
+        assert!(!bounded_by_until.matches(date(2023, 1, 3)));
+    }
+
+    #[test]
+    fn by_set_pos_picks_the_last_weekday_of_the_month() {
+        let rule = parse_rrule(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            date(2023, 1, 31),
+        )
+        .unwrap();
+
+        assert!(rule.matches(date(2023, 1, 31)));
+        assert!(rule.matches(date(2023, 2, 28)));
+        assert!(!rule.matches(date(2023, 1, 30)));
+    }
+
+    #[test]
+    fn missing_freq_is_rejected() {
+        assert!(parse_rrule("INTERVAL=2;BYDAY=MO", date(2023, 1, 2)).is_none());
+    }
+
+    #[test]
+    fn parse_by_day_rejects_malformed_tokens() {
+        assert_eq!(super::parse_by_day("XX"), None);
+        assert_eq!(super::parse_by_day("MO").map(|d| d.weekday), Some(Weekday::Mon));
+        assert_eq!(super::parse_by_day("-1SU").map(|d| d.ordinal), Some(Some(-1)));
+    }
+}
@@ -1,12 +1,16 @@
+use alloc::rc::Rc;
+
 use crate::{
     ocpi::cdr::{Cdr, OcpiCdrDimension, OcpiChargingPeriod},
+    pricer::HolidayCalendar,
     types::{
         electricity::{Ampere, Kw, Kwh},
+        number::Number,
         time::DateTime,
     },
 };
 
-use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, Offset, TimeZone, Weekday};
 use chrono_tz::Tz;
 
 pub struct ChargeSession {
@@ -15,7 +19,7 @@ pub struct ChargeSession {
 }
 
 impl ChargeSession {
-    pub fn new(cdr: &Cdr, local_timezone: Tz) -> Self {
+    pub fn new(cdr: &Cdr, local_timezone: Tz, holiday_calendar: Rc<HolidayCalendar>) -> Self {
         let mut periods: Vec<ChargePeriod> = Vec::new();
 
         for (i, period) in cdr.charging_periods.iter().enumerate() {
@@ -28,7 +32,12 @@ impl ChargeSession {
             let next = if let Some(last) = periods.last() {
                 last.next(period, end_date_time)
             } else {
-                ChargePeriod::new(local_timezone, period, end_date_time)
+                ChargePeriod::new(
+                    local_timezone,
+                    Rc::clone(&holiday_calendar),
+                    period,
+                    end_date_time,
+                )
             };
 
             periods.push(next);
@@ -39,9 +48,58 @@ impl ChargeSession {
             start_date_time: cdr.start_date_time,
         }
     }
+
+    /// Project this session into a plottable, time-ordered series of cumulative energy and
+    /// duration, with one point per period's end instant.
+    pub fn series(&self) -> SessionSeries {
+        let points: Vec<SessionPoint> = self
+            .periods
+            .iter()
+            .map(|period| SessionPoint {
+                date_time: period.end_instant.date_time,
+                total_energy: period.end_instant.total_energy,
+                total_charging_duration: period.end_instant.total_charging_duration,
+                total_duration: period.end_instant.total_duration,
+            })
+            .collect();
+
+        let end_date_time = points
+            .last()
+            .map_or(self.start_date_time, |point| point.date_time);
+        let final_energy = points.last().map_or(Kwh::zero(), |point| point.total_energy);
+
+        SessionSeries {
+            points,
+            time_bounds: (self.start_date_time, end_date_time),
+            energy_bounds: (Kwh::zero(), final_energy),
+        }
+    }
+}
+
+/// A single point in a [`SessionSeries`]: the cumulative energy and durations at one period's end
+/// instant.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionPoint {
+    pub date_time: DateTime,
+    pub total_energy: Kwh,
+    pub total_charging_duration: Duration,
+    pub total_duration: Duration,
+}
+
+/// A time-ordered, plottable series of cumulative [`SessionPoint`]s across a [`ChargeSession`].
+///
+/// `time_bounds` and `energy_bounds` give a plotting backend the `(min, max)` range for each axis
+/// (the session's start/end instant, and zero to its final energy) so it can map values onto a
+/// pixel range without re-deriving those bounds from the points itself.
+#[derive(Debug, Clone)]
+pub struct SessionSeries {
+    pub points: Vec<SessionPoint>,
+    pub time_bounds: (DateTime, DateTime),
+    pub energy_bounds: (Kwh, Kwh),
 }
 
 /// Describes the properties of a single charging period.
+#[derive(Clone)]
 pub struct ChargePeriod {
     /// Holds properties that are valid for the entirety of this period.
     pub period_data: PeriodData,
@@ -54,9 +112,17 @@ pub struct ChargePeriod {
 impl ChargePeriod {
     /// Construct a new `ChargePeriod` with zeroed values. Should be the first period in the
     /// session.
-    fn new(local_timezone: Tz, period: &OcpiChargingPeriod, end_date_time: DateTime) -> Self {
-        let charge_state = PeriodData::new(period);
-        let start_instant = InstantData::zero(period.start_date_time, local_timezone);
+    fn new(
+        local_timezone: Tz,
+        holiday_calendar: Rc<HolidayCalendar>,
+        period: &OcpiChargingPeriod,
+        end_date_time: DateTime,
+    ) -> Self {
+        let mut charge_state = PeriodData::new(period);
+        charge_state.derive_charging_duration(period.start_date_time, end_date_time);
+
+        let start_instant =
+            InstantData::zero(period.start_date_time, local_timezone, holiday_calendar);
         let end_instant = start_instant.next(&charge_state, end_date_time);
 
         Self {
@@ -68,7 +134,9 @@ impl ChargePeriod {
 
     /// Construct a period with the properties of `period` that ends on `end_date_time` which succeeds `self`.
     fn next(&self, period: &OcpiChargingPeriod, end_date_time: DateTime) -> Self {
-        let charge_state = PeriodData::new(period);
+        let mut charge_state = PeriodData::new(period);
+        charge_state.derive_charging_duration(self.end_instant.date_time, end_date_time);
+
         let start_instant = self.end_instant.clone();
         let end_instant = start_instant.next(&charge_state, end_date_time);
 
@@ -78,10 +146,161 @@ impl ChargePeriod {
             end_instant,
         }
     }
+
+    /// Split this period into two at the instant its `total_duration` first reaches `boundary`,
+    /// which must fall strictly between `start_instant.total_duration` and
+    /// `end_instant.total_duration`.
+    ///
+    /// Only `charging_duration`/`parking_duration` are apportioned between the two halves, in
+    /// proportion to how much of the period's wall-clock length falls before/after the boundary
+    /// (`total_duration` grows 1:1 with wall-clock time, so this is also where that elapsed time
+    /// crosses the boundary). Every other volume (energy, current, power, reservation) stays with
+    /// the first half, since those aren't what a duration restriction is measuring.
+    pub(crate) fn split_at(&self, boundary: Duration) -> (Self, Self) {
+        let boundary_date_time =
+            self.start_instant.date_time + (boundary - self.start_instant.total_duration);
+
+        let elapsed_millis = boundary_date_time
+            .signed_duration_since(self.start_instant.date_time)
+            .num_milliseconds();
+        let total_millis = self
+            .end_instant
+            .date_time
+            .signed_duration_since(self.start_instant.date_time)
+            .num_milliseconds()
+            .max(1);
+
+        let split_duration = |volume: Option<Duration>| -> (Option<Duration>, Option<Duration>) {
+            let Some(volume) = volume else {
+                return (None, None);
+            };
+
+            let before_millis =
+                volume.num_milliseconds().saturating_mul(elapsed_millis) / total_millis;
+            let before = Duration::milliseconds(before_millis);
+
+            (Some(before), Some(volume - before))
+        };
+
+        let (charging_before, charging_after) =
+            split_duration(self.period_data.charging_duration);
+        let (parking_before, parking_after) = split_duration(self.period_data.parking_duration);
+
+        let before_data = PeriodData {
+            charging_duration: charging_before,
+            parking_duration: parking_before,
+            ..self.period_data.clone()
+        };
+        let mid_instant = self.start_instant.next(&before_data, boundary_date_time);
+
+        let before = Self {
+            period_data: before_data,
+            start_instant: self.start_instant.clone(),
+            end_instant: mid_instant.clone(),
+        };
+
+        let after = Self {
+            period_data: PeriodData {
+                charging_duration: charging_after,
+                parking_duration: parking_after,
+                ..PeriodData::empty()
+            },
+            start_instant: mid_instant,
+            end_instant: self.end_instant.clone(),
+        };
+
+        (before, after)
+    }
+
+    /// Split this period into two at `date_time`, which must fall strictly between
+    /// `start_instant.date_time` and `end_instant.date_time`.
+    ///
+    /// Unlike [`Self::split_at`], every accumulated volume (`energy`, `charging_duration`,
+    /// `parking_duration`, `reservation_duration`) is apportioned between the two halves in
+    /// proportion to how much of the period's wall-clock length falls before/after `date_time`,
+    /// with any rounding absorbed into the second half so the two halves sum to the original
+    /// exactly. This is meant for splitting a period whose restriction regime (tariff element
+    /// validity) changes mid-period, rather than one that merely crosses a duration threshold,
+    /// so unlike [`Self::split_at`] there's no single dimension the split is "about" that should
+    /// keep the undivided remainder.
+    pub(crate) fn split_at_date_time(&self, date_time: DateTime) -> (Self, Self) {
+        let elapsed_millis = date_time
+            .signed_duration_since(self.start_instant.date_time)
+            .num_milliseconds();
+        let total_millis = self
+            .end_instant
+            .date_time
+            .signed_duration_since(self.start_instant.date_time)
+            .num_milliseconds()
+            .max(1);
+
+        let split_duration = |volume: Option<Duration>| -> (Option<Duration>, Option<Duration>) {
+            let Some(volume) = volume else {
+                return (None, None);
+            };
+
+            let before_millis =
+                volume.num_milliseconds().saturating_mul(elapsed_millis) / total_millis;
+            let before = Duration::milliseconds(before_millis);
+
+            (Some(before), Some(volume - before))
+        };
+
+        let split_energy = |volume: Option<Kwh>| -> (Option<Kwh>, Option<Kwh>) {
+            let Some(volume) = volume else {
+                return (None, None);
+            };
+
+            let fraction = Number::from(elapsed_millis)
+                .checked_div(Number::from(total_millis))
+                .expect("divisor is clamped to at least 1");
+
+            let before = Kwh::from(Number::from(volume).saturating_mul(fraction));
+
+            (Some(before), Some(volume.saturating_sub(before)))
+        };
+
+        let (charging_before, charging_after) =
+            split_duration(self.period_data.charging_duration);
+        let (parking_before, parking_after) = split_duration(self.period_data.parking_duration);
+        let (reservation_before, reservation_after) =
+            split_duration(self.period_data.reservation_duration);
+        let (energy_before, energy_after) = split_energy(self.period_data.energy);
+
+        let before_data = PeriodData {
+            charging_duration: charging_before,
+            parking_duration: parking_before,
+            reservation_duration: reservation_before,
+            energy: energy_before,
+            ..self.period_data.clone()
+        };
+        let mid_instant = self.start_instant.next(&before_data, date_time);
+
+        let before = Self {
+            period_data: before_data,
+            start_instant: self.start_instant.clone(),
+            end_instant: mid_instant.clone(),
+        };
+
+        let after = Self {
+            period_data: PeriodData {
+                charging_duration: charging_after,
+                parking_duration: parking_after,
+                reservation_duration: reservation_after,
+                energy: energy_after,
+                ..self.period_data.clone()
+            },
+            start_instant: mid_instant,
+            end_instant: self.end_instant.clone(),
+        };
+
+        (before, after)
+    }
 }
 
 /// This describes the properties in the charge session that a valid during a certain period. For
 /// example the `duration` field is the charge duration during a certain charging period.
+#[derive(Clone)]
 pub struct PeriodData {
     pub max_current: Option<Ampere>,
     pub min_current: Option<Ampere>,
@@ -98,18 +317,28 @@ pub struct PeriodData {
 #[derive(Clone)]
 pub struct InstantData {
     local_timezone: Tz,
+    holiday_calendar: Rc<HolidayCalendar>,
     pub date_time: DateTime,
     pub total_charging_duration: Duration,
+    pub total_parking_duration: Duration,
+    pub total_reservation_duration: Duration,
     pub total_duration: Duration,
     pub total_energy: Kwh,
 }
 
 impl InstantData {
-    fn zero(date_time: DateTime, local_timezone: Tz) -> Self {
+    pub(crate) fn zero(
+        date_time: DateTime,
+        local_timezone: Tz,
+        holiday_calendar: Rc<HolidayCalendar>,
+    ) -> Self {
         Self {
             date_time,
             local_timezone,
+            holiday_calendar,
             total_charging_duration: Duration::zero(),
+            total_parking_duration: Duration::zero(),
+            total_reservation_duration: Duration::zero(),
             total_duration: Duration::zero(),
             total_energy: Kwh::zero(),
         }
@@ -134,6 +363,20 @@ impl InstantData {
                 .unwrap_or_else(Duration::max_value);
         }
 
+        if let Some(duration) = state.parking_duration {
+            next.total_parking_duration = next
+                .total_parking_duration
+                .checked_add(&duration)
+                .unwrap_or_else(Duration::max_value);
+        }
+
+        if let Some(duration) = state.reservation_duration {
+            next.total_reservation_duration = next
+                .total_reservation_duration
+                .checked_add(&duration)
+                .unwrap_or_else(Duration::max_value);
+        }
+
         if let Some(energy) = state.energy {
             next.total_energy = next.total_energy.saturating_add(energy);
         }
@@ -141,6 +384,22 @@ impl InstantData {
         next
     }
 
+    /// The portion of [`Self::total_parking_duration`] that's actually billable: the total minus
+    /// a configured free-parking `grace`, floored at zero so a grace larger than the parking time
+    /// doesn't produce a negative duration.
+    ///
+    /// Mirrors the "maximum free parking" allowance common in curbside parking tariffs, letting a
+    /// caller (the calculator, or [`crate::explain`]) separate the free portion of parking time
+    /// from the portion that should actually be billed.
+    pub fn billable_parking_duration(&self, grace: Duration) -> Duration {
+        (self.total_parking_duration - grace).max(Duration::zero())
+    }
+
+    /// The time zone local times in this session are evaluated against.
+    pub(crate) fn local_timezone(&self) -> Tz {
+        self.local_timezone
+    }
+
     pub fn local_time(&self) -> NaiveTime {
         self.date_time.with_timezone(&self.local_timezone).time()
     }
@@ -151,12 +410,99 @@ impl InstantData {
             .date_naive()
     }
 
+    /// The weekday of [`Self::local_date`], with holiday substitution applied when the holiday
+    /// calendar configures a `treat_as_weekday`.
     pub fn local_weekday(&self) -> Weekday {
-        self.date_time.with_timezone(&self.local_timezone).weekday()
+        let date = self.local_date();
+        let actual = self.date_time.with_timezone(&self.local_timezone).weekday();
+
+        self.holiday_calendar.weekday(date, actual)
+    }
+
+    /// Resolve `time` on this instant's local calendar date back to a concrete UTC instant,
+    /// using the same policy as [`crate::tariff::local_date_time`]: the earlier of the two
+    /// instants on a fall-back day where `time` occurs twice, or, on a spring-forward day where
+    /// `time` is skipped, `time` moved forward by the length of the gap.
+    ///
+    /// Needed because, unlike [`Self::local_time`]/[`Self::local_date`] (UTC to local, always
+    /// unambiguous), the reverse direction is what restriction boundary generation needs and that
+    /// round-trip isn't unambiguous across a DST transition.
+    pub fn wall_clock_to_utc(&self, time: NaiveTime) -> Option<DateTime> {
+        let local = self.local_date().and_time(time);
+
+        self.local_timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                let before = self
+                    .local_timezone
+                    .offset_from_utc_datetime(&(local - Duration::days(1)))
+                    .fix();
+                let utc = local - Duration::seconds(i64::from(before.local_minus_utc()));
+
+                Some(chrono::Utc.from_utc_datetime(&utc).with_timezone(&self.local_timezone))
+            })
+            .map(|local| local.with_timezone(&chrono::Utc))
+    }
+
+    /// The UTC offset in effect at [`Self::date_time`], e.g. to detect whether a period's
+    /// `start_instant` and `end_instant` straddle a daylight-saving transition.
+    pub fn utc_offset(&self) -> FixedOffset {
+        self.local_timezone
+            .offset_from_utc_datetime(&self.date_time.naive_utc())
+            .fix()
+    }
+
+    /// Whether [`Self::local_date`] is a holiday according to the configured calendar.
+    pub fn is_holiday(&self) -> bool {
+        self.holiday_calendar.is_holiday(self.local_date())
+    }
+
+    /// Whether the day before [`Self::local_date`] is a holiday according to the configured
+    /// calendar.
+    pub(crate) fn is_day_before_holiday(&self) -> bool {
+        self.holiday_calendar
+            .is_holiday(self.local_date() - Duration::days(1))
     }
 }
 
 impl PeriodData {
+    /// A period that reported no volume for any dimension.
+    pub(crate) fn empty() -> Self {
+        Self {
+            max_current: None,
+            min_current: None,
+            max_power: None,
+            min_power: None,
+            charging_duration: None,
+            parking_duration: None,
+            reservation_duration: None,
+            energy: None,
+        }
+    }
+
+    /// Fall back to this period's actual elapsed wall-clock time for `charging_duration`, for a
+    /// period whose CDR supplied none of `Time`/`ParkingTime`/`ReservationTime` at all.
+    ///
+    /// Real-world CDRs frequently omit per-period dimensions entirely and rely on consecutive
+    /// periods' `start_date_time` (and the CDR's own `end_date_time`) to imply how long each
+    /// period lasted; without this, such a period would never bill a `Time` component even while
+    /// actively charging. Only applies when every duration dimension is absent, so a period that
+    /// did report e.g. `ParkingTime` (and just not `Time`) is left alone rather than also being
+    /// billed as charging time.
+    fn derive_charging_duration(&mut self, start_date_time: DateTime, end_date_time: DateTime) {
+        if self.charging_duration.is_none()
+            && self.parking_duration.is_none()
+            && self.reservation_duration.is_none()
+        {
+            self.charging_duration = Some(
+                end_date_time
+                    .signed_duration_since(start_date_time)
+                    .max(Duration::zero()),
+            );
+        }
+    }
+
     fn new(period: &OcpiChargingPeriod) -> Self {
         let mut inst = Self {
             parking_duration: None,
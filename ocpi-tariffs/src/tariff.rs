@@ -1,16 +1,28 @@
+use alloc::rc::Rc;
+
+use chrono::{Duration, NaiveTime, Offset, TimeZone};
+use chrono_tz::Tz;
 use serde::Serialize;
 
 use crate::ocpi::tariff::{
     CompatibilityVat, OcpiPriceComponent, OcpiTariff, OcpiTariffElement, TariffDimensionType,
 };
 
-use crate::pricer::PeriodWarnings;
+use crate::pricer::{HolidayCalendar, PeriodWarnings};
 use crate::restriction::{collect_restrictions, Restriction};
-use crate::session::ChargePeriod;
-use crate::types::{money::Money, time::DateTime};
+use crate::session::{ChargePeriod, InstantData, PeriodData};
+use crate::types::{
+    electricity::{Kw, Kwh},
+    money::{Money, Price},
+    number::Number,
+    time::{DateTime, HoursDecimal},
+};
+use crate::Result;
 
 pub struct Tariff {
     pub id: String,
+    pub min_price: Option<Price>,
+    pub max_price: Option<Price>,
     elements: Vec<TariffElement>,
     start_date_time: Option<DateTime>,
     end_date_time: Option<DateTime>,
@@ -27,6 +39,8 @@ impl Tariff {
 
         Self {
             id: tariff.id.clone(),
+            min_price: tariff.min_price,
+            max_price: tariff.max_price,
             start_date_time: tariff.start_date_time,
             end_date_time: tariff.end_date_time,
             elements,
@@ -52,22 +66,31 @@ impl Tariff {
                 continue;
             }
 
-            if components.time.is_none() {
-                components.time = tariff_element.components.time;
-            }
+            if tariff_element.is_reservation() {
+                if components.reservation.is_none() {
+                    components.reservation = tariff_element
+                        .components
+                        .time
+                        .or(tariff_element.components.flat);
+                }
+            } else {
+                if components.time.is_none() {
+                    components.time = tariff_element.components.time;
+                }
 
-            if components.parking.is_none() {
-                components.parking = tariff_element.components.parking;
+                if components.parking.is_none() {
+                    components.parking = tariff_element.components.parking;
+                }
+
+                if components.flat.is_none() {
+                    components.flat = tariff_element.components.flat;
+                }
             }
 
             if components.energy.is_none() {
                 components.energy = tariff_element.components.energy;
             }
 
-            if components.flat.is_none() {
-                components.flat = tariff_element.components.flat;
-            }
-
             if components.has_all_components() {
                 break;
             }
@@ -85,6 +108,347 @@ impl Tariff {
 
         is_after_start && is_before_end
     }
+
+    /// The smallest `max_duration` restriction, among this tariff's elements, that `period`
+    /// crosses: its `start_instant.total_duration` is still under the bound but its
+    /// `end_instant.total_duration` has reached or passed it.
+    pub(crate) fn max_duration_crossed(&self, period: &ChargePeriod) -> Option<Duration> {
+        self.elements
+            .iter()
+            .filter_map(TariffElement::max_duration)
+            .filter(|&max_duration| {
+                period.start_instant.total_duration < max_duration
+                    && period.end_instant.total_duration >= max_duration
+            })
+            .min()
+    }
+
+    /// The earliest instant, strictly between `period.start_instant.date_time` and
+    /// `period.end_instant.date_time`, at which this tariff's element validity could change.
+    ///
+    /// A tariff element's restrictions can only change validity either at a `StartTime`/
+    /// `EndTime`/`WrappingTime` threshold, or at local midnight (since every date-based
+    /// restriction, such as `DayOfWeek` or `Holiday`, is evaluated against the local calendar
+    /// date, which only changes at midnight). This collects both kinds of candidate, expanded
+    /// over every local day the period touches, and returns the smallest one inside the period.
+    pub(crate) fn next_restriction_boundary(&self, period: &ChargePeriod) -> Option<DateTime> {
+        let local_timezone = period.start_instant.local_timezone();
+        let start_date = period.start_instant.local_date();
+        let end_date = period.end_instant.local_date();
+
+        let times: Vec<NaiveTime> = self
+            .elements
+            .iter()
+            .flat_map(TariffElement::restriction_times)
+            .chain(core::iter::once(
+                NaiveTime::from_hms_opt(0, 0, 0).expect("midnight is a valid time"),
+            ))
+            .collect();
+
+        let mut date = start_date;
+        let mut boundaries = Vec::new();
+
+        while date <= end_date {
+            for &time in &times {
+                if let Some(candidate) = local_date_time(date, time, local_timezone) {
+                    boundaries.push(candidate);
+                }
+            }
+
+            let Some(next_date) = date.succ_opt() else {
+                break;
+            };
+            date = next_date;
+        }
+
+        boundaries
+            .into_iter()
+            .filter(|&candidate| {
+                candidate > period.start_instant.date_time
+                    && candidate < period.end_instant.date_time
+            })
+            .min()
+    }
+
+    /// The [`PriceComponents`] that would apply to a brand new session starting at `start` (in
+    /// `time_zone`), evaluated at its very first instant, i.e. with no duration or energy
+    /// accumulated yet. This is exactly how [`crate::session::ChargeSession`]'s own first period
+    /// is constructed, so it lets [`Self::max_duration_for_budget`]/
+    /// [`Self::max_energy_for_budget`] reuse [`Self::active_components`] without pricing a real
+    /// [`crate::ocpi::cdr::Cdr`] first.
+    ///
+    /// Restrictions that depend on accumulated duration/energy/power (`MinKwh`, `MaxDuration`,
+    /// `MinPower`, ...) are therefore evaluated as if the session had just started, not as they
+    /// would be once it has run for a while; a long enough affordable session could cross into a
+    /// restriction boundary, and thus a different price, than the one used here.
+    fn components_at(
+        &self,
+        start: DateTime,
+        time_zone: Tz,
+        holiday_calendar: &HolidayCalendar,
+    ) -> PriceComponents {
+        let instant = InstantData::zero(start, time_zone, Rc::new(holiday_calendar.clone()));
+        let period = ChargePeriod {
+            period_data: PeriodData {
+                max_current: None,
+                min_current: None,
+                max_power: None,
+                min_power: None,
+                charging_duration: None,
+                parking_duration: None,
+                reservation_duration: None,
+                energy: None,
+            },
+            start_instant: instant.clone(),
+            end_instant: instant,
+        };
+
+        self.active_components(&period, &mut PeriodWarnings::default())
+    }
+
+    /// The longest session, starting at `start`, that fits under `budget` using the price
+    /// components active at that instant (see [`Self::components_at`] for what that does and
+    /// doesn't account for).
+    ///
+    /// `Flat` is charged once, `Time` at its per-hour price and `Energy` (if `assumed_power` is
+    /// given) at its per-kWh price scaled by the power assumed to be delivered throughout the
+    /// session. Whichever of `Time`/`Energy` determines the answer is floored to the last full
+    /// `step_size` block that still fits the budget, since a tariff bills a partial block as a
+    /// whole one; when both are active, only `Time`'s `step_size` is used; the `Energy`
+    /// component's own step rounding is not modelled jointly with it.
+    ///
+    /// Returns `None` if there's no way to relate `budget` to a duration: an `Energy` component
+    /// is active but `assumed_power` wasn't given, or neither `Time` nor `Energy` is active at
+    /// all (so nothing but the one-off `Flat` fee is billed, and the true answer is unbounded).
+    pub fn max_duration_for_budget(
+        &self,
+        budget: Money,
+        start: DateTime,
+        time_zone: Tz,
+        holiday_calendar: &HolidayCalendar,
+        assumed_power: Option<Kw>,
+    ) -> Result<Option<HoursDecimal>> {
+        let components = self.components_at(start, time_zone, holiday_calendar);
+
+        let flat_cost = components.flat.map_or(Money::zero(), |component| component.price);
+        if flat_cost > budget {
+            return Ok(Some(HoursDecimal::zero()));
+        }
+        let remaining: Number = budget.saturating_sub(flat_cost).into();
+
+        if components.energy.is_some() && assumed_power.is_none() {
+            return Ok(None);
+        }
+
+        let energy_rate = components.energy.zip(assumed_power).map(|(energy, power)| {
+            energy.price.kwh_cost(Kwh::from(power))
+        });
+
+        let (rate, step_hours) = match (components.time, energy_rate) {
+            (Some(time), energy_rate) => {
+                let rate = energy_rate.map_or(time.price, |extra| time.price.saturating_add(extra));
+
+                let step_hours = if time.step_size > 0 {
+                    let seconds = Number::from(time.step_size);
+                    Some(HoursDecimal::from_seconds_number(seconds)?.as_num_hours_number())
+                } else {
+                    None
+                };
+
+                (rate, step_hours)
+            }
+            (None, Some(energy_rate)) => {
+                let energy = components
+                    .energy
+                    .expect("energy_rate is only set from components.energy");
+
+                let step_hours = if energy.step_size > 0 {
+                    let power = assumed_power
+                        .expect("energy_rate is only set when assumed_power is Some");
+                    let step_energy: Number =
+                        Kwh::from_watt_hours(Number::from(energy.step_size)).into();
+                    let power: Number = Kwh::from(power).into();
+
+                    Some(step_energy.checked_div(power)?)
+                } else {
+                    None
+                };
+
+                (energy_rate, step_hours)
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let rate: Number = rate.into();
+        let Some(hours) = max_volume_for_budget(remaining, rate, step_hours) else {
+            return Ok(None);
+        };
+
+        Ok(Some(HoursDecimal::from_hours_number(hours)?))
+    }
+
+    /// The most energy, delivered starting at `start`, that fits under `budget` using the price
+    /// components active at that instant. The mirror image of
+    /// [`Self::max_duration_for_budget`]; see it for the shared `Flat`/step-size/`assumed_power`
+    /// semantics and the cases in which `None` is returned.
+    pub fn max_energy_for_budget(
+        &self,
+        budget: Money,
+        start: DateTime,
+        time_zone: Tz,
+        holiday_calendar: &HolidayCalendar,
+        assumed_power: Option<Kw>,
+    ) -> Result<Option<Kwh>> {
+        let components = self.components_at(start, time_zone, holiday_calendar);
+
+        let flat_cost = components.flat.map_or(Money::zero(), |component| component.price);
+        if flat_cost > budget {
+            return Ok(Some(Kwh::zero()));
+        }
+        let remaining: Number = budget.saturating_sub(flat_cost).into();
+
+        if components.time.is_some() && assumed_power.is_none() {
+            return Ok(None);
+        }
+
+        let time_rate = components
+            .time
+            .zip(assumed_power)
+            .map(|(time, power)| -> Result<Money> {
+                // One kWh delivered at `power` kW takes `1 / power` hours to charge.
+                let power: Number = Kwh::from(power).into();
+                let hours_per_kwh = Number::from(1).checked_div(power)?;
+
+                Ok(time.price.time_cost(HoursDecimal::from_hours_number(hours_per_kwh)?))
+            })
+            .transpose()?;
+
+        let (rate, step_kwh) = match (components.energy, time_rate) {
+            (Some(energy), time_rate) => {
+                let rate =
+                    time_rate.map_or(energy.price, |extra| energy.price.saturating_add(extra));
+
+                let step_kwh: Option<Number> = if energy.step_size > 0 {
+                    Some(Kwh::from_watt_hours(Number::from(energy.step_size)).into())
+                } else {
+                    None
+                };
+
+                (rate, step_kwh)
+            }
+            (None, Some(time_rate)) => {
+                let time = components
+                    .time
+                    .expect("time_rate is only set from components.time");
+
+                let step_kwh = if time.step_size > 0 {
+                    let power = assumed_power
+                        .expect("time_rate is only set when assumed_power is Some");
+                    let step_hours =
+                        HoursDecimal::from_seconds_number(Number::from(time.step_size))?;
+                    let power: Number = Kwh::from(power).into();
+
+                    Some(power.saturating_mul(step_hours.as_num_hours_number()))
+                } else {
+                    None
+                };
+
+                (time_rate, step_kwh)
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let rate: Number = rate.into();
+        let Some(kwh) = max_volume_for_budget(remaining, rate, step_kwh) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Kwh::from(kwh)))
+    }
+}
+
+/// The largest multiple of `step` (in the same unit `rate` is priced per) whose cost at `rate`
+/// fits under `budget`, floored to a full `step` block — mirroring how `step_size` billing rounds
+/// a volume up to the next block, so overshooting the returned volume by any amount would push
+/// the bill past `budget`. A `step` of `None` means the dimension isn't billed in discrete
+/// blocks, so `budget` translates directly into a continuous volume.
+///
+/// Returns `None` if `rate` is zero or negative: spending doesn't change with volume, so there's
+/// no finite answer.
+fn max_volume_for_budget(budget: Number, rate: Number, step: Option<Number>) -> Option<Number> {
+    if rate <= Number::default() {
+        return None;
+    }
+
+    match step {
+        Some(step) if step > Number::default() => {
+            let cost_per_step = rate.saturating_mul(step);
+            let steps = budget.checked_div(cost_per_step).ok()?.floor();
+
+            Some(steps.saturating_mul(step))
+        }
+        _ => budget.checked_div(rate).ok(),
+    }
+}
+
+/// Resolve a local calendar date and time of day to a concrete instant in `timezone`, preferring
+/// the earlier of the two possible instants on a fall-back-DST day. On a spring-forward day where
+/// the local time is skipped, the time is read with the UTC offset in effect before the gap, which
+/// moves it forward by the length of the gap (02:30 in a 02:00 to 03:00 gap resolves to 03:30).
+pub(crate) fn local_date_time(
+    date: chrono::NaiveDate,
+    time: NaiveTime,
+    timezone: chrono_tz::Tz,
+) -> Option<DateTime> {
+    let naive = date.and_time(time);
+
+    timezone
+        .from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            let before = timezone.offset_from_utc_datetime(&(naive - Duration::days(1))).fix();
+            let utc = naive - Duration::seconds(i64::from(before.local_minus_utc()));
+
+            Some(chrono::Utc.from_utc_datetime(&utc).with_timezone(&timezone))
+        })
+        .map(|local| local.with_timezone(&chrono::Utc))
+}
+
+/// The earliest instant, strictly between `period.start_instant.date_time` and
+/// `period.end_instant.date_time`, at which `period`'s timezone's UTC offset changes (a
+/// daylight-saving transition).
+///
+/// Local time-of-day restrictions are evaluated against a single representative instant of each
+/// (possibly split) period, which is only correct if that period's offset is constant throughout;
+/// this locates the transition so the caller can split the period there, same as it already splits
+/// on a restriction or `max_duration` boundary.
+pub(crate) fn next_offset_transition(period: &ChargePeriod) -> Option<DateTime> {
+    let timezone = period.start_instant.local_timezone();
+    let mut low = period.start_instant.date_time;
+    let mut high = period.end_instant.date_time;
+
+    if timezone.offset_from_utc_datetime(&low.naive_utc()).fix()
+        == timezone.offset_from_utc_datetime(&high.naive_utc()).fix()
+    {
+        return None;
+    }
+
+    // The offsets at `low` and `high` differ, so a transition lies somewhere in between; binary
+    // search it down to the second, since chrono-tz offsets are piecewise-constant and change at
+    // a single instant.
+    let low_offset = timezone.offset_from_utc_datetime(&low.naive_utc()).fix();
+
+    while high - low > Duration::seconds(1) {
+        let mid = low + (high - low) / 2;
+
+        if timezone.offset_from_utc_datetime(&mid.naive_utc()).fix() == low_offset {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Some(high)
 }
 
 struct TariffElement {
@@ -144,6 +508,40 @@ impl TariffElement {
 
         true
     }
+
+    /// Whether this element only applies during a reservation, i.e. it carries a
+    /// [`Restriction::Reservation`].
+    fn is_reservation(&self) -> bool {
+        self.restrictions
+            .iter()
+            .any(|restriction| matches!(restriction, Restriction::Reservation))
+    }
+
+    /// This element's `MaxDuration` restriction, if it carries one.
+    fn max_duration(&self) -> Option<Duration> {
+        self.restrictions
+            .iter()
+            .find_map(|restriction| match restriction {
+                &Restriction::MaxDuration(max_duration) => Some(max_duration),
+                _ => None,
+            })
+    }
+
+    /// Every time-of-day threshold at which one of this element's restrictions could start or
+    /// stop applying.
+    fn restriction_times(&self) -> Vec<NaiveTime> {
+        self.restrictions
+            .iter()
+            .flat_map(|restriction| match *restriction {
+                Restriction::StartTime(time) | Restriction::EndTime(time) => vec![time],
+                Restriction::WrappingTime {
+                    start_time,
+                    end_time,
+                } => vec![start_time, end_time],
+                _ => Vec::new(),
+            })
+            .collect()
+    }
 }
 
 pub struct PriceComponents {
@@ -151,6 +549,7 @@ pub struct PriceComponents {
     pub energy: Option<PriceComponent>,
     pub parking: Option<PriceComponent>,
     pub time: Option<PriceComponent>,
+    pub reservation: Option<PriceComponent>,
 }
 
 impl PriceComponents {
@@ -160,6 +559,7 @@ impl PriceComponents {
             energy: None,
             parking: None,
             time: None,
+            reservation: None,
         }
     }
 
@@ -196,3 +596,218 @@ impl PriceComponent {
         }
     }
 }
+
+/// Shared fixtures for the test modules below, so a field added to `PeriodData` or `ChargePeriod`
+/// only needs updating in one place.
+#[cfg(test)]
+mod test_fixtures {
+    use std::rc::Rc;
+
+    use chrono::{TimeZone, Utc};
+
+    use super::{DateTime, Tz};
+    use crate::{
+        pricer::HolidayCalendar,
+        session::{ChargePeriod, InstantData, PeriodData},
+    };
+
+    pub(super) fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    /// A period spanning `start..end` in `Europe/Amsterdam`, with every dimension empty.
+    pub(super) fn period(start: DateTime, end: DateTime) -> ChargePeriod {
+        let calendar = Rc::new(HolidayCalendar::default());
+        let tz = Tz::Europe__Amsterdam;
+
+        ChargePeriod {
+            period_data: PeriodData::empty(),
+            start_instant: InstantData::zero(start, tz, calendar.clone()),
+            end_instant: InstantData::zero(end, tz, calendar),
+        }
+    }
+}
+
+#[cfg(test)]
+mod local_date_time_tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::{local_date_time, test_fixtures::utc, Tz};
+
+    #[test]
+    fn fall_back_overlap_prefers_the_earliest_instant() {
+        // On 2023-10-29, Europe/Amsterdam clocks went back from 03:00 CEST to 02:00 CET, so
+        // 02:30 local occurred twice: first at 00:30 UTC (CEST, UTC+2), then again at 01:30 UTC
+        // (CET, UTC+1).
+        let date = NaiveDate::from_ymd_opt(2023, 10, 29).unwrap();
+        let time = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+
+        let resolved = local_date_time(date, time, Tz::Europe__Amsterdam).unwrap();
+
+        assert_eq!(resolved, utc(2023, 10, 29, 0, 30));
+    }
+
+    #[test]
+    fn spring_forward_gap_resolves_to_the_instant_that_exists() {
+        // On 2023-03-26, Europe/Amsterdam clocks jumped from 02:00 CET straight to 03:00 CEST,
+        // so 02:30 local never happened; `from_local_datetime` has no instant at all, so the
+        // gap fallback (the CET offset from before the jump) must resolve it to 03:30 CEST
+        // (01:30 UTC).
+        let date = NaiveDate::from_ymd_opt(2023, 3, 26).unwrap();
+        let time = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+
+        let resolved = local_date_time(date, time, Tz::Europe__Amsterdam).unwrap();
+
+        assert_eq!(resolved, utc(2023, 3, 26, 1, 30));
+    }
+}
+
+#[cfg(test)]
+mod next_offset_transition_tests {
+    use super::next_offset_transition;
+    use super::test_fixtures::{period, utc};
+
+    #[test]
+    fn finds_the_spring_forward_transition() {
+        // Europe/Amsterdam jumped from CET (UTC+1) to CEST (UTC+2) at 01:00 UTC on 2023-03-26.
+        let period = period(utc(2023, 3, 25, 22, 0), utc(2023, 3, 26, 4, 0));
+
+        assert_eq!(next_offset_transition(&period), Some(utc(2023, 3, 26, 1, 0)));
+    }
+
+    #[test]
+    fn finds_the_fall_back_transition() {
+        // Europe/Amsterdam fell back from CEST (UTC+2) to CET (UTC+1) at 01:00 UTC on 2023-10-29.
+        let period = period(utc(2023, 10, 28, 22, 0), utc(2023, 10, 29, 4, 0));
+
+        assert_eq!(next_offset_transition(&period), Some(utc(2023, 10, 29, 1, 0)));
+    }
+
+    #[test]
+    fn no_transition_within_a_constant_offset_period() {
+        let period = period(utc(2023, 6, 1, 10, 0), utc(2023, 6, 1, 18, 0));
+
+        assert_eq!(next_offset_transition(&period), None);
+    }
+}
+
+#[cfg(test)]
+mod next_restriction_boundary_tests {
+    use chrono::NaiveTime;
+
+    use super::test_fixtures::{period, utc};
+    use super::{PriceComponents, Tariff, TariffElement};
+    use crate::restriction::Restriction;
+
+    fn night_tariff() -> Tariff {
+        let element = TariffElement {
+            restrictions: vec![
+                Restriction::StartTime(NaiveTime::from_hms_opt(23, 0, 0).unwrap()),
+                Restriction::EndTime(NaiveTime::from_hms_opt(7, 0, 0).unwrap()),
+            ],
+            components: PriceComponents::new(),
+        };
+
+        Tariff {
+            id: "night".to_string(),
+            min_price: None,
+            max_price: None,
+            elements: vec![element],
+            start_date_time: None,
+            end_date_time: None,
+        }
+    }
+
+    #[test]
+    fn finds_the_nearer_of_a_restriction_time_and_local_midnight() {
+        // Europe/Amsterdam is UTC+1 in January, so 22:00 local is 21:00 UTC, 23:00 local is
+        // 22:00 UTC, and local midnight is 23:00 UTC.
+        let tariff = night_tariff();
+        let period = period(utc(2023, 1, 1, 21, 0), utc(2023, 1, 2, 7, 0));
+
+        let boundary = tariff.next_restriction_boundary(&period);
+
+        assert_eq!(boundary, Some(utc(2023, 1, 1, 22, 0)));
+    }
+
+    #[test]
+    fn no_boundary_inside_a_period_that_stays_within_one_restriction_window() {
+        let tariff = night_tariff();
+        // Local 01:00-05:00 on 2023-01-02: inside the night window, crossing neither 23:00/07:00
+        // nor local midnight.
+        let period = period(utc(2023, 1, 2, 0, 0), utc(2023, 1, 2, 4, 0));
+
+        assert_eq!(tariff.next_restriction_boundary(&period), None);
+    }
+}
+
+#[cfg(test)]
+mod parking_time_component_tests {
+    use chrono::Duration;
+
+    use super::test_fixtures::{period, utc};
+    use super::{PriceComponent, PriceComponents, Tariff, TariffElement};
+    use crate::{
+        ocpi::tariff::CompatibilityVat,
+        pricer::PeriodWarnings,
+        session::{ChargePeriod, PeriodData},
+        types::money::Money,
+    };
+
+    // A tariff with only a PARKING_TIME component, no time/flat/energy components and no
+    // restrictions, to isolate parking-time component selection from everything else
+    // `active_components` also does.
+    fn parking_only_tariff() -> Tariff {
+        let element = TariffElement {
+            restrictions: vec![],
+            components: PriceComponents {
+                parking: Some(PriceComponent {
+                    tariff_element_index: 0,
+                    price: Money::zero(),
+                    vat: CompatibilityVat::Unknown,
+                    step_size: 1,
+                }),
+                ..PriceComponents::new()
+            },
+        };
+
+        Tariff {
+            id: "parking-only".to_string(),
+            min_price: None,
+            max_price: None,
+            elements: vec![element],
+            start_date_time: None,
+            end_date_time: None,
+        }
+    }
+
+    fn period_with_parking_duration(duration: Duration) -> ChargePeriod {
+        let start = utc(2023, 1, 1, 10, 0);
+        let end = utc(2023, 1, 1, 10, 30);
+
+        ChargePeriod {
+            period_data: PeriodData {
+                parking_duration: Some(duration),
+                ..PeriodData::empty()
+            },
+            ..period(start, end)
+        }
+    }
+
+    // Regression test for a request that (incorrectly, for this tree) assumed `PeriodData::new`
+    // drops the `ParkingTime` CDR dimension and that pricing a `PARKING_TIME` tariff element
+    // panics. Neither is true here: the dimension is captured as `PeriodData::parking_duration`
+    // (see `PeriodData::new` in `session.rs`) and selected into `PriceComponents::parking` below,
+    // same as `time`/`flat`/`energy`.
+    #[test]
+    fn parking_time_component_is_selected_when_active() {
+        let tariff = parking_only_tariff();
+        let period = period_with_parking_duration(Duration::minutes(20));
+        let mut warnings = PeriodWarnings::new();
+
+        let components = tariff.active_components(&period, &mut warnings);
+
+        assert!(components.parking.is_some());
+    }
+}
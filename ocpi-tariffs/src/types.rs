@@ -1,3 +1,6 @@
+/// OCPI Types related to currencies.
+pub(crate) mod currency;
+
 /// OCPI Types related to electricity.
 pub mod electricity;
 
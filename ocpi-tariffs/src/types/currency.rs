@@ -0,0 +1,37 @@
+/// The number of decimal places a currency's minor unit is quoted in, per ISO 4217.
+///
+/// Most currencies use 2 (cents, pence, ...), but a handful of exceptions exist: some have no
+/// minor unit at all (`JPY`, `KRW`, ...) and a few use 3 (`BHD`, `KWD`, `OMR`, ...). Unknown or
+/// unrecognized codes fall back to 2, matching the OCPI default.
+pub(crate) fn minor_units(currency: &str) -> u32 {
+    match currency.to_ascii_uppercase().as_str() {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::minor_units;
+
+    #[test]
+    fn looks_up_zero_decimal_currencies() {
+        assert_eq!(minor_units("JPY"), 0);
+        assert_eq!(minor_units("krw"), 0);
+    }
+
+    #[test]
+    fn looks_up_three_decimal_currencies() {
+        assert_eq!(minor_units("BHD"), 3);
+        assert_eq!(minor_units("KWD"), 3);
+    }
+
+    #[test]
+    fn defaults_to_two_decimals() {
+        assert_eq!(minor_units("EUR"), 2);
+        assert_eq!(minor_units("USD"), 2);
+        assert_eq!(minor_units("???"), 2);
+    }
+}
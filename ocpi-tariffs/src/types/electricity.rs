@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,12 @@ use super::number::Number;
 #[serde(transparent)]
 pub struct Kwh(Number);
 
+impl From<Number> for Kwh {
+    fn from(value: Number) -> Self {
+        Self(value)
+    }
+}
+
 impl Kwh {
     pub(crate) fn zero() -> Self {
         Self(Number::default())
@@ -31,13 +37,13 @@ impl Kwh {
     pub(crate) fn from_watt_hours(num: Number) -> Self {
         Self(
             num.checked_div(Number::from(1000))
-                .unwrap_or_else(|| unreachable!("divisor is non-zero")),
+                .expect("divisor is a non-zero constant"),
         )
     }
 
     /// Round this number to the OCPI specified amount of decimals.
     pub fn with_scale(self) -> Self {
-        Self(self.0.with_scale())
+        Self(self.0.with_default_scale())
     }
 }
 
@@ -54,7 +60,7 @@ impl From<Kwh> for Number {
 }
 
 impl Display for Kwh {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:.4}", self.0)
     }
 }
@@ -70,6 +76,13 @@ impl From<Kw> for rust_decimal::Decimal {
     }
 }
 
+impl From<Kw> for Kwh {
+    /// The energy delivered in one hour sustained at this power, since 1 kWh is 1 kW for 1 hour.
+    fn from(value: Kw) -> Self {
+        Self(value.0)
+    }
+}
+
 /// A value of amperes.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
 #[serde(transparent)]
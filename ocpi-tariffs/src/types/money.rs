@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +56,27 @@ impl Price {
             },
         }
     }
+
+    /// Clamp `excl_vat` and `incl_vat` independently to the bounds of `min`/`max`, such as a
+    /// tariff's `min_price`/`max_price`. Either bound is optional and applied independently, a
+    /// `None` value in `min`/`max` (or in `min.incl_vat`/`max.incl_vat`) leaves that side
+    /// unbounded.
+    #[must_use]
+    pub fn clamp(self, min: Option<Self>, max: Option<Self>) -> Self {
+        let excl_vat = min.map_or(self.excl_vat, |min| self.excl_vat.at_least(min.excl_vat));
+        let excl_vat = max.map_or(excl_vat, |max| excl_vat.at_most(max.excl_vat));
+
+        let incl_vat = self.incl_vat.map(|incl_vat| {
+            let incl_vat = min
+                .and_then(|min| min.incl_vat)
+                .map_or(incl_vat, |min| incl_vat.at_least(min));
+
+            max.and_then(|max| max.incl_vat)
+                .map_or(incl_vat, |max| incl_vat.at_most(max))
+        });
+
+        Self { excl_vat, incl_vat }
+    }
 }
 
 impl Default for Price {
@@ -65,7 +86,7 @@ impl Default for Price {
 }
 
 /// A monetary amount, the currency is dependant on the specified tariff.
-#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(transparent)]
 pub struct Money(Number);
 
@@ -74,6 +95,18 @@ impl Money {
         Self(Number::default())
     }
 
+    /// Clamp this amount to be no less than `min`.
+    #[must_use]
+    pub fn at_least(self, min: Self) -> Self {
+        self.max(min)
+    }
+
+    /// Clamp this amount to be no more than `max`.
+    #[must_use]
+    pub fn at_most(self, max: Self) -> Self {
+        self.min(max)
+    }
+
     /// Round this number to the OCPI specified amount of decimals.
     #[must_use]
     pub fn with_default_scale(self) -> Self {
@@ -135,7 +168,7 @@ impl From<Money> for Number {
 }
 
 impl Display for Money {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -155,13 +188,13 @@ impl Vat {
     pub(crate) fn as_fraction(self) -> Number {
         self.0
             .checked_div(100.into())
-            .unwrap_or_else(|| unreachable!("divisor is non-zero"))
+            .expect("divisor is a non-zero constant")
             .saturating_add(1.into())
     }
 }
 
 impl Display for Vat {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -1,7 +1,29 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+/// An arithmetic failure while combining two [`Number`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberError {
+    /// Division by a zero divisor.
+    DivideByZero,
+    /// The result doesn't fit in a [`rust_decimal::Decimal`].
+    Overflow,
+}
+
+impl Display for NumberError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let display = match self {
+            Self::DivideByZero => "division by zero",
+            Self::Overflow => "numeric overflow",
+        };
+
+        f.write_str(display)
+    }
+}
+
+impl std::error::Error for NumberError {}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub(crate) struct Number(rust_decimal::Decimal);
 
@@ -10,26 +32,61 @@ impl Number {
         Self(self.0.ceil())
     }
 
-    pub(crate) fn with_scale(mut self) -> Self {
+    pub(crate) fn floor(self) -> Self {
+        Self(self.0.floor())
+    }
+
+    /// Round this number to the OCPI specified amount of decimals (4).
+    pub(crate) fn with_default_scale(mut self) -> Self {
         self.0.rescale(4);
         self
     }
 
-    pub(crate) fn checked_div(self, other: Self) -> Self {
-        Self(self.0.checked_div(other.0).expect("divide by zero"))
+    /// Round this number to the specified amount of decimals.
+    pub(crate) fn with_scale(mut self, scale: u32) -> Self {
+        self.0.rescale(scale);
+        self
     }
 
+    /// Fallible division: reports a zero divisor or an overflowing result instead of panicking,
+    /// so that it surfaces as an [`crate::Error`] rather than crashing the whole pricing run.
+    pub(crate) fn checked_div(self, other: Self) -> Result<Self, NumberError> {
+        if other.0.is_zero() {
+            return Err(NumberError::DivideByZero);
+        }
+
+        self.0
+            .checked_div(other.0)
+            .map(Self)
+            .ok_or(NumberError::Overflow)
+    }
+
+    /// Saturating subtraction. Used for accumulating running totals across a session. It
+    /// saturates at `Decimal::MIN`, not at zero: a negative difference is returned as is.
     pub(crate) fn saturating_sub(self, other: Self) -> Self {
         Self(self.0.saturating_sub(other.0))
     }
 
+    /// Saturating addition. Used for accumulating running totals across a session; clamping at
+    /// `Decimal::MAX` on an astronomically large (and in practice unreachable) sum is preferable
+    /// to aborting an otherwise valid session.
     pub(crate) fn saturating_add(self, other: Self) -> Self {
         Self(self.0.saturating_add(other.0))
     }
 
+    /// Saturating multiplication. Used for accumulating running totals across a session, for the
+    /// same reason as [`Self::saturating_add`].
     pub(crate) fn saturating_mul(self, other: Self) -> Self {
         Self(self.0.saturating_mul(other.0))
     }
+
+    /// Fallible multiplication: reports an overflowing result instead of silently clamping it, for
+    /// callers that opted into [`crate::pricer::Pricer::with_checked_arithmetic`] and would rather
+    /// surface a malformed input (e.g. an absurd step-size volume) as an error than price it as a
+    /// plausible-looking approximation.
+    pub(crate) fn checked_mul(self, other: Self) -> Result<Self, NumberError> {
+        self.0.checked_mul(other.0).map(Self).ok_or(NumberError::Overflow)
+    }
 }
 
 impl<'de> Deserialize<'de> for Number {
@@ -90,7 +147,7 @@ impl TryFrom<Number> for i64 {
 }
 
 impl Display for Number {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
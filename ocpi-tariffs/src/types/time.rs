@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use chrono::Duration;
 use chrono_tz::Tz;
@@ -39,7 +39,7 @@ impl Serialize for HoursDecimal {
 }
 
 impl Display for HoursDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let duration = self.0;
         let seconds = duration.num_seconds() % SECS_IN_MIN;
         let minutes = (duration.num_seconds() / SECS_IN_MIN) % MINS_IN_HOUR;
@@ -69,7 +69,7 @@ impl HoursDecimal {
     pub(crate) fn as_num_seconds_number(&self) -> Number {
         Number::from(self.0.num_milliseconds())
             .checked_div(Number::from(MILLIS_IN_SEC))
-            .unwrap_or_else(|| unreachable!("divisor is non-zero"))
+            .expect("divisor is a non-zero constant")
     }
 
     /// Convert into decimal representation.
@@ -81,7 +81,7 @@ impl HoursDecimal {
     pub(crate) fn as_num_hours_number(&self) -> Number {
         Number::from(self.0.num_milliseconds())
             .checked_div(Number::from(MILLIS_IN_SEC * SECS_IN_MIN * MINS_IN_HOUR))
-            .unwrap_or_else(|| unreachable!("divisor is non-zero"))
+            .expect("divisor is a non-zero constant")
     }
 
     pub(crate) fn from_seconds_number(seconds: Number) -> Result<Self, Error> {
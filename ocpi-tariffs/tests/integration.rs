@@ -23,9 +23,10 @@ pub fn validate_cdr(cdr: &Cdr, tariff: OcpiTariff) -> Result<(), ocpi_tariffs::E
 
     assert_eq!(
         cdr.total_cost,
-        report.total_cost.unwrap_or_default().with_scale(),
+        report.total_cost.unwrap_or_default().with_default_scale(),
         "total_cost"
     );
+    assert_eq!(cdr.currency, report.currency, "currency");
 
     assert_eq!(
         cdr.total_energy,
@@ -34,7 +35,10 @@ pub fn validate_cdr(cdr: &Cdr, tariff: OcpiTariff) -> Result<(), ocpi_tariffs::E
     );
     assert_eq!(
         cdr.total_energy_cost.unwrap_or_default(),
-        report.total_energy_cost.unwrap_or_default().with_scale(),
+        report
+            .total_energy_cost
+            .unwrap_or_default()
+            .with_default_scale(),
         "total_energy_cost"
     );
 
@@ -42,7 +46,10 @@ pub fn validate_cdr(cdr: &Cdr, tariff: OcpiTariff) -> Result<(), ocpi_tariffs::E
 
     assert_eq!(
         cdr.total_time_cost.unwrap_or_default(),
-        report.total_time_cost.unwrap_or_default().with_scale(),
+        report
+            .total_time_cost
+            .unwrap_or_default()
+            .with_default_scale(),
         "total_time_cost"
     );
 
@@ -54,7 +61,10 @@ pub fn validate_cdr(cdr: &Cdr, tariff: OcpiTariff) -> Result<(), ocpi_tariffs::E
 
     assert_eq!(
         cdr.total_parking_cost.unwrap_or_default(),
-        report.total_parking_cost.unwrap_or_default().with_scale(),
+        report
+            .total_parking_cost
+            .unwrap_or_default()
+            .with_default_scale(),
         "total_parking_cost"
     );
 
@@ -63,13 +73,16 @@ pub fn validate_cdr(cdr: &Cdr, tariff: OcpiTariff) -> Result<(), ocpi_tariffs::E
         report
             .total_reservation_cost
             .unwrap_or_default()
-            .with_scale(),
+            .with_default_scale(),
         "total_reservation_cost"
     );
 
     assert_eq!(
         cdr.total_fixed_cost.unwrap_or_default(),
-        report.total_fixed_cost.unwrap_or_default().with_scale(),
+        report
+            .total_fixed_cost
+            .unwrap_or_default()
+            .with_default_scale(),
         "total_fixed_cost"
     );
 